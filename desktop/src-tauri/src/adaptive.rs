@@ -0,0 +1,187 @@
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike, Weekday};
+use std::time::Duration;
+
+use crate::config::{AppConfig, WorkingHours};
+use crate::github::{self, CiStatus, DashboardResponse};
+
+/// One of my PRs updated this recently makes the dashboard active.
+const ACTIVE_WINDOW_SECS: u64 = 5 * 60;
+
+/// Each period without changes doubles the interval, up to the maximum.
+const QUIET_PERIOD: Duration = Duration::from_secs(15 * 60);
+const MAX_QUIET_DOUBLINGS: u32 = 6;
+
+/// Below this fraction of rate limit left, the interval stretches in
+/// proportion: at half of it left, twice as long.
+const RATE_LIMIT_HEADROOM: f64 = 0.25;
+
+/// Whether any of my PRs is waiting on CI or changed in the last few
+/// minutes, so its next change is likely soon. Pending CI bypasses the
+/// enrichment cache, so a fast poll sees CI finish.
+fn is_active(dashboard: &DashboardResponse, now: u64) -> bool {
+    dashboard.my_prs.iter().any(|pr| {
+        pr.ci_status == Some(CiStatus::Pending)
+            || github::parse_iso_timestamp(&pr.updated_at)
+                .is_some_and(|updated| now.saturating_sub(updated) < ACTIVE_WINDOW_SECS)
+    })
+}
+
+fn in_working_hours<Tz: TimeZone>(hours: Option<&WorkingHours>, now: &DateTime<Tz>) -> bool {
+    let Some(hours) = hours else {
+        return true;
+    };
+    if hours.weekdays_only && matches!(now.weekday(), Weekday::Sat | Weekday::Sun) {
+        return false;
+    }
+    let hour = now.hour();
+    if hours.start_hour < hours.end_hour {
+        (hours.start_hour..hours.end_hour).contains(&hour)
+    } else {
+        hour >= hours.start_hour || hour < hours.end_hour
+    }
+}
+
+/// How long to wait before the next scheduled fetch. Without adaptive polling
+/// this is `poll_interval_ms`. With it, the interval is the minimum while my
+/// PRs are active, the maximum outside working hours, and otherwise
+/// `poll_interval_ms` doubled for every `QUIET_PERIOD` of `quiet_for`; then
+/// stretched as the rate limit runs low and kept within the bounds.
+pub fn next_interval(
+    config: &AppConfig,
+    dashboard: Option<&DashboardResponse>,
+    quiet_for: Duration,
+) -> Duration {
+    let base = Duration::from_millis(config.poll_interval_ms);
+    let adaptive = &config.adaptive_polling;
+    if !adaptive.enabled {
+        return base;
+    }
+    let min = Duration::from_millis(adaptive.min_interval_ms);
+    let max = Duration::from_millis(adaptive.max_interval_ms);
+
+    let mut interval = if !in_working_hours(adaptive.working_hours.as_ref(), &Local::now()) {
+        max
+    } else if dashboard.is_some_and(|d| is_active(d, github::unix_now())) {
+        min
+    } else {
+        let doublings = (quiet_for.as_secs() / QUIET_PERIOD.as_secs()) as u32;
+        base * 2u32.pow(doublings.min(MAX_QUIET_DOUBLINGS))
+    };
+
+    if let Some(headroom) = github::rate_limit_headroom() {
+        if headroom < RATE_LIMIT_HEADROOM {
+            // Nothing left: as slow as allowed until the window resets
+            interval = if headroom <= 0.0 {
+                max
+            } else {
+                interval.mul_f64(RATE_LIMIT_HEADROOM / headroom)
+            };
+        }
+    }
+
+    interval.max(min).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AdaptivePolling;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn adaptive_config() -> AppConfig {
+        AppConfig {
+            poll_interval_ms: 60_000,
+            adaptive_polling: AdaptivePolling {
+                enabled: true,
+                min_interval_ms: 30_000,
+                max_interval_ms: 900_000,
+                working_hours: None,
+            },
+            ..AppConfig::default()
+        }
+    }
+
+    /// A dashboard with one of my PRs, last updated long ago.
+    fn dashboard_with_ci(ci_status: Option<CiStatus>) -> DashboardResponse {
+        serde_json::from_value(json!({
+            "myPrs": [{
+                "id": 1,
+                "number": 1,
+                "title": "PR",
+                "url": "https://github.com/org/repo/pull/1",
+                "repo": "org/repo",
+                "account": "github.com",
+                "author": { "login": "me", "avatarUrl": "" },
+                "turnStatus": "their-turn",
+                "turnDebugInfo": null,
+                "isDraft": false,
+                "createdAt": "2024-05-01T00:00:00Z",
+                "updatedAt": "2024-05-01T00:00:00Z",
+                "turnSince": null,
+                "waitingFor": null,
+                "labels": [],
+                "reviewers": [],
+                "reviewSummary": "",
+                "size": null,
+                "ciStatus": ci_status,
+            }],
+            "reviewRequests": [],
+            "githubUsername": "me",
+            "fetchedAt": "2024-05-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn disabled_uses_poll_interval() {
+        let mut config = adaptive_config();
+        config.adaptive_polling.enabled = false;
+        assert_eq!(next_interval(&config, None, secs(3600)), secs(60));
+    }
+
+    #[test]
+    fn doubles_for_each_quiet_period_up_to_max() {
+        let config = adaptive_config();
+        assert_eq!(next_interval(&config, None, secs(0)), secs(60));
+        assert_eq!(next_interval(&config, None, secs(30 * 60)), secs(240));
+        assert_eq!(next_interval(&config, None, secs(24 * 3600)), secs(900));
+    }
+
+    #[test]
+    fn pending_ci_polls_at_min() {
+        let config = adaptive_config();
+        let pending = dashboard_with_ci(Some(CiStatus::Pending));
+        assert_eq!(next_interval(&config, Some(&pending), secs(3600)), secs(30));
+        let done = dashboard_with_ci(Some(CiStatus::Success));
+        assert_eq!(next_interval(&config, Some(&done), secs(0)), secs(60));
+    }
+
+    #[test]
+    fn working_hours_span_midnight_and_skip_weekends() {
+        let overnight = WorkingHours {
+            start_hour: 22,
+            end_hour: 6,
+            weekdays_only: false,
+        };
+        // 2024-05-01 is a Wednesday
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2024, 5, day, hour, 0, 0).unwrap();
+        assert!(in_working_hours(Some(&overnight), &at(1, 23)));
+        assert!(in_working_hours(Some(&overnight), &at(1, 5)));
+        assert!(!in_working_hours(Some(&overnight), &at(1, 12)));
+
+        let office = WorkingHours {
+            start_hour: 9,
+            end_hour: 17,
+            weekdays_only: true,
+        };
+        assert!(in_working_hours(Some(&office), &at(1, 9)));
+        assert!(!in_working_hours(Some(&office), &at(1, 17)));
+        assert!(!in_working_hours(Some(&office), &at(4, 10)));
+        assert!(in_working_hours(None, &at(4, 3)));
+    }
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::config::{self, GitHubAccount};
+use crate::github::DashboardResponse;
+use crate::secrets;
+
+const CACHE_FILE: &str = "dashboard.json";
+
+/// `dirs::data_dir()/gh-dash`, next to but separate from the config dir so
+/// the cache can be deleted freely.
+fn get_data_dir() -> Result<PathBuf, String> {
+    let data_dir =
+        dirs::data_dir().ok_or_else(|| "Failed to determine data directory".to_string())?;
+    Ok(data_dir.join("gh-dash"))
+}
+
+fn get_cache_path() -> Result<PathBuf, String> {
+    Ok(get_data_dir()?.join(CACHE_FILE))
+}
+
+/// Persist the last successful dashboard. PR titles and reviewers are not
+/// meant to be world-readable, so it is written like config.json.
+pub fn save(dashboard: &DashboardResponse) -> Result<(), String> {
+    let path = get_cache_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let json = serde_json::to_string(dashboard)
+        .map_err(|e| format!("Failed to serialize dashboard cache: {}", e))?;
+    secrets::write_private_file(&path, json.as_bytes())
+        .map_err(|e| format!("Failed to write dashboard cache: {}", e))
+}
+
+/// Delete the cache, e.g. when a token changes and the cached PRs may belong
+/// to someone else.
+pub fn clear() {
+    let Ok(path) = get_cache_path() else {
+        return;
+    };
+    if let Err(e) = fs::remove_file(&path) {
+        if e.kind() != ErrorKind::NotFound {
+            eprintln!("Failed to delete dashboard cache: {}", e);
+        }
+    }
+}
+
+/// Whether a fetch now could use the accounts labelled `cached`. The primary
+/// github.com account only joins when it has a token, which isn't resolved
+/// here, so a list with or without it matches.
+fn matches_configured(cached: &[&str]) -> Option<bool> {
+    let config = config::read_config().ok()?;
+    let listed: Vec<&str> = config.accounts.iter().map(|a| a.label.as_str()).collect();
+    if cached == listed.as_slice() {
+        return Some(true);
+    }
+    let primary = GitHubAccount::github_com(String::new()).label;
+    Some(
+        !config.lists_github_com()
+            && cached.split_first() == Some((&primary.as_str(), listed.as_slice())),
+    )
+}
+
+/// The cached dashboard marked stale, or `None` when there is no usable cache.
+/// A cache from an older app version that no longer parses is ignored, and
+/// so is one fetched with different accounts than are configured now.
+pub fn load() -> Option<DashboardResponse> {
+    let contents = fs::read_to_string(get_cache_path().ok()?).ok()?;
+    let mut dashboard: DashboardResponse = serde_json::from_str(&contents).ok()?;
+    let cached_labels: Vec<&str> = dashboard
+        .accounts
+        .iter()
+        .map(|a| a.label.as_str())
+        .collect();
+    if !matches_configured(&cached_labels)? {
+        return None;
+    }
+    dashboard.stale = true;
+    // Changes were relative to a fetch the UI never saw
+    dashboard.changes.clear();
+    Some(dashboard)
+}
+
+/// The last successful dashboard, for showing something before the first
+/// live fetch completes. Its `fetched_at` says how old it is.
+#[tauri::command]
+pub fn get_cached_dashboard() -> Option<DashboardResponse> {
+    load()
+}
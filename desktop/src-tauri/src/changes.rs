@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::github::{CiStatus, DashboardPR, DashboardResponse, ReviewerState, TurnStatus};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum DashboardChangeKind {
+    /// A review request that wasn't on the dashboard before
+    NewReviewRequest,
+    /// Their turn -> my turn
+    BecameMyTurn,
+    /// No longer returned by the searches: merged, closed, or the request
+    /// was withdrawn
+    LeftDashboard,
+    NewApproval,
+    ChangesRequested,
+    /// CI on the head commit went from passing, pending or unknown to failing
+    CiFailed,
+    MergeableStateChanged,
+}
+
+/// One difference between two consecutive fetches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardChange {
+    pub kind: DashboardChangeKind,
+    pub url: String,
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub account: String,
+    /// "my-prs" or "review-requests", from the newer fetch when the PR is
+    /// still on the dashboard
+    pub section: String,
+    /// Reviewer login for approvals and change requests; "old -> new" for
+    /// mergeable state changes
+    pub detail: Option<String>,
+}
+
+impl DashboardChange {
+    fn new(kind: DashboardChangeKind, pr: &DashboardPR, section: &str) -> Self {
+        Self {
+            kind,
+            url: pr.url.clone(),
+            repo: pr.repo.clone(),
+            number: pr.number,
+            title: pr.title.clone(),
+            account: pr.account.clone(),
+            section: section.to_string(),
+            detail: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: String) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+}
+
+/// Every PR in a response by URL, with the section it is listed in. Grouped
+/// bot PRs are review requests.
+fn prs_by_url(response: &DashboardResponse) -> HashMap<&str, (&DashboardPR, &'static str)> {
+    let my_prs = response.my_prs.iter().map(|pr| (pr, "my-prs"));
+    let review_requests = response
+        .review_requests
+        .iter()
+        .chain(&response.bot_prs)
+        .map(|pr| (pr, "review-requests"));
+    my_prs
+        .chain(review_requests)
+        .map(|(pr, section)| (pr.url.as_str(), (pr, section)))
+        .collect()
+}
+
+/// Latest review state per reviewer login, ignoring team entries.
+fn reviewer_states(pr: &DashboardPR) -> HashMap<&str, ReviewerState> {
+    pr.reviewers
+        .iter()
+        .filter(|r| r.team_slug.is_none())
+        .map(|r| (r.login.as_str(), r.state))
+        .collect()
+}
+
+/// Compare two consecutive fetches. PRs of accounts that failed in `current`
+/// are not reported as having left, and PRs of accounts that failed in or
+/// were missing from `previous` are not reported as new.
+pub fn diff_dashboards(
+    previous: &DashboardResponse,
+    current: &DashboardResponse,
+) -> Vec<DashboardChange> {
+    let before = prs_by_url(previous);
+    let after = prs_by_url(current);
+    let failed_accounts: HashSet<&str> = current
+        .accounts
+        .iter()
+        .filter(|a| a.error.is_some())
+        .map(|a| a.label.as_str())
+        .collect();
+    // Accounts `previous` listed PRs for; PRs of any other account aren't new
+    let seen_accounts: HashSet<&str> = previous
+        .accounts
+        .iter()
+        .filter(|a| a.error.is_none())
+        .map(|a| a.label.as_str())
+        .collect();
+
+    let mut changes: Vec<DashboardChange> = Vec::new();
+    // Walk the newer lists in display order so changes come out sorted
+    let ordered = current
+        .my_prs
+        .iter()
+        .chain(&current.review_requests)
+        .chain(&current.bot_prs);
+    for pr in ordered {
+        let section = after[pr.url.as_str()].1;
+        let Some(&(old, _)) = before.get(pr.url.as_str()) else {
+            if section == "review-requests" && seen_accounts.contains(pr.account.as_str()) {
+                changes.push(DashboardChange::new(
+                    DashboardChangeKind::NewReviewRequest,
+                    pr,
+                    section,
+                ));
+            }
+            continue;
+        };
+
+        if old.turn_status == TurnStatus::TheirTurn && pr.turn_status == TurnStatus::MyTurn {
+            changes.push(DashboardChange::new(
+                DashboardChangeKind::BecameMyTurn,
+                pr,
+                section,
+            ));
+        }
+
+        let old_states = reviewer_states(old);
+        for reviewer in pr.reviewers.iter().filter(|r| r.team_slug.is_none()) {
+            let (login, state) = (reviewer.login.as_str(), reviewer.state);
+            let kind = match state {
+                ReviewerState::Approved => DashboardChangeKind::NewApproval,
+                ReviewerState::ChangesRequested => DashboardChangeKind::ChangesRequested,
+                _ => continue,
+            };
+            if old_states.get(login) != Some(&state) {
+                changes
+                    .push(DashboardChange::new(kind, pr, section).with_detail(login.to_string()));
+            }
+        }
+
+        if pr.ci_status == Some(CiStatus::Failure) && old.ci_status != Some(CiStatus::Failure) {
+            changes.push(DashboardChange::new(
+                DashboardChangeKind::CiFailed,
+                pr,
+                section,
+            ));
+        }
+
+        // "unknown" means GitHub is still computing it
+        let known = |state: &Option<String>| state.clone().filter(|s| s != "unknown");
+        if let (Some(old_state), Some(new_state)) =
+            (known(&old.mergeable_state), known(&pr.mergeable_state))
+        {
+            if old_state != new_state {
+                changes.push(
+                    DashboardChange::new(DashboardChangeKind::MergeableStateChanged, pr, section)
+                        .with_detail(format!("{} -> {}", old_state, new_state)),
+                );
+            }
+        }
+    }
+
+    let left = previous
+        .my_prs
+        .iter()
+        .chain(&previous.review_requests)
+        .chain(&previous.bot_prs)
+        .filter(|pr| !after.contains_key(pr.url.as_str()))
+        .filter(|pr| !failed_accounts.contains(pr.account.as_str()));
+    for pr in left {
+        let section = before[pr.url.as_str()].1;
+        changes.push(DashboardChange::new(
+            DashboardChangeKind::LeftDashboard,
+            pr,
+            section,
+        ));
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{DashboardAccountStatus, DashboardAuthor, DashboardReviewer};
+
+    fn pr(account: &str, number: u64, turn_status: TurnStatus) -> DashboardPR {
+        DashboardPR {
+            id: number,
+            number,
+            title: format!("PR {}", number),
+            url: format!("https://github.com/org/repo/pull/{}", number),
+            repo: "org/repo".to_string(),
+            account: account.to_string(),
+            author: DashboardAuthor {
+                login: "author".to_string(),
+                avatar_url: String::new(),
+                is_bot: false,
+            },
+            turn_status,
+            turn_debug_info: None,
+            is_draft: false,
+            created_at: "2024-05-01T00:00:00Z".to_string(),
+            updated_at: "2024-05-01T00:00:00Z".to_string(),
+            turn_since: None,
+            waiting_for: None,
+            labels: Vec::new(),
+            reviewers: Vec::new(),
+            review_summary: String::new(),
+            size: None,
+            mergeable_state: None,
+            ci_status: None,
+            skeleton: false,
+        }
+    }
+
+    fn account(label: &str, error: Option<&str>) -> DashboardAccountStatus {
+        DashboardAccountStatus {
+            label: label.to_string(),
+            login: Some("me".to_string()),
+            error: error.map(String::from),
+            token_expires_at: None,
+        }
+    }
+
+    fn dashboard(
+        accounts: Vec<DashboardAccountStatus>,
+        my_prs: Vec<DashboardPR>,
+        review_requests: Vec<DashboardPR>,
+    ) -> DashboardResponse {
+        DashboardResponse {
+            my_prs,
+            review_requests,
+            bot_prs: Vec::new(),
+            github_username: "me".to_string(),
+            accounts,
+            warnings: Vec::new(),
+            changes: Vec::new(),
+            fetched_at: "2024-05-01T00:00:00Z".to_string(),
+            stale: false,
+            refresh_stats: Default::default(),
+            generation: 0,
+        }
+    }
+
+    fn kinds(changes: &[DashboardChange]) -> Vec<DashboardChangeKind> {
+        changes.iter().map(|c| c.kind).collect()
+    }
+
+    #[test]
+    fn reports_new_review_request() {
+        let previous = dashboard(vec![account("work", None)], vec![], vec![]);
+        let current = dashboard(
+            vec![account("work", None)],
+            vec![],
+            vec![pr("work", 1, TurnStatus::MyTurn)],
+        );
+        assert_eq!(
+            kinds(&diff_dashboards(&previous, &current)),
+            [DashboardChangeKind::NewReviewRequest]
+        );
+    }
+
+    #[test]
+    fn prs_of_a_recovered_account_are_not_new() {
+        let previous = dashboard(
+            vec![account("work", Some("Bad credentials"))],
+            vec![],
+            vec![],
+        );
+        let current = dashboard(
+            vec![account("work", None)],
+            vec![],
+            vec![pr("work", 1, TurnStatus::MyTurn)],
+        );
+        assert!(diff_dashboards(&previous, &current).is_empty());
+    }
+
+    #[test]
+    fn prs_of_an_added_account_are_not_new() {
+        let previous = dashboard(vec![account("work", None)], vec![], vec![]);
+        let current = dashboard(
+            vec![account("work", None), account("ghes", None)],
+            vec![],
+            vec![pr("ghes", 1, TurnStatus::MyTurn)],
+        );
+        assert!(diff_dashboards(&previous, &current).is_empty());
+    }
+
+    #[test]
+    fn prs_of_a_failed_account_have_not_left() {
+        let previous = dashboard(
+            vec![account("work", None), account("ghes", None)],
+            vec![pr("work", 1, TurnStatus::TheirTurn)],
+            vec![pr("ghes", 2, TurnStatus::MyTurn)],
+        );
+        let current = dashboard(
+            vec![account("work", None), account("ghes", Some("timed out"))],
+            vec![],
+            vec![],
+        );
+        let changes = diff_dashboards(&previous, &current);
+        assert_eq!(kinds(&changes), [DashboardChangeKind::LeftDashboard]);
+        assert_eq!(changes[0].number, 1);
+    }
+
+    #[test]
+    fn reports_turn_approval_ci_and_mergeable_changes() {
+        let mut old = pr("work", 1, TurnStatus::TheirTurn);
+        old.ci_status = Some(CiStatus::Pending);
+        old.mergeable_state = Some("blocked".to_string());
+        let previous = dashboard(vec![account("work", None)], vec![old], vec![]);
+        let mut updated = pr("work", 1, TurnStatus::MyTurn);
+        updated.ci_status = Some(CiStatus::Failure);
+        updated.mergeable_state = Some("dirty".to_string());
+        updated.reviewers.push(DashboardReviewer {
+            login: "alice".to_string(),
+            avatar_url: None,
+            team_slug: None,
+            state: ReviewerState::Approved,
+            submitted_at: None,
+            requested_again: false,
+        });
+        let current = dashboard(vec![account("work", None)], vec![updated], vec![]);
+
+        let changes = diff_dashboards(&previous, &current);
+        assert_eq!(
+            kinds(&changes),
+            [
+                DashboardChangeKind::BecameMyTurn,
+                DashboardChangeKind::NewApproval,
+                DashboardChangeKind::CiFailed,
+                DashboardChangeKind::MergeableStateChanged,
+            ]
+        );
+        assert_eq!(changes[1].detail.as_deref(), Some("alice"));
+        assert_eq!(changes[3].detail.as_deref(), Some("blocked -> dirty"));
+    }
+
+    #[test]
+    fn unknown_mergeable_state_is_not_a_change() {
+        let mut old = pr("work", 1, TurnStatus::TheirTurn);
+        old.mergeable_state = Some("clean".to_string());
+        let mut new = old.clone();
+        new.mergeable_state = Some("unknown".to_string());
+        let previous = dashboard(vec![account("work", None)], vec![old], vec![]);
+        let current = dashboard(vec![account("work", None)], vec![new], vec![]);
+        assert!(diff_dashboards(&previous, &current).is_empty());
+    }
+}
@@ -1,79 +1,1787 @@
+use notify::{EventKind, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+use crate::cache;
+use crate::changes::DashboardChangeKind;
+use crate::credentials::{self, TokenSource};
+use crate::github::TurnStatus;
+use crate::layers;
+use crate::secrets::{self, SecretStore, SecretStoreKind};
+
+/// Current config.json schema version. Bump it and append a step to
+/// `MIGRATIONS` whenever a field is renamed or reshaped.
+const CONFIG_VERSION: u64 = 3;
+
+/// REST API root for github.com.
+pub const GITHUB_API: &str = "https://api.github.com";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
+    /// Schema version; files without one are version 0
+    #[serde(default)]
+    pub version: u64,
+    /// Legacy plaintext token. Migrated into the secret store on first load
+    /// and left empty on disk afterwards.
     #[serde(default)]
     pub github_pat: String,
     #[serde(default = "default_poll_interval")]
     pub poll_interval_ms: u64,
+    #[serde(default)]
+    pub sort: SortConfig,
+    #[serde(default)]
+    pub size_thresholds: SizeThresholds,
+    #[serde(default)]
+    pub bot_prs: BotPrMode,
+    /// Additional accounts merged into one dashboard. `github_pat` joins them
+    /// as a github.com account unless one is listed; see `effective_accounts`.
+    #[serde(default)]
+    pub accounts: Vec<GitHubAccount>,
+    #[serde(default)]
+    pub secret_store: SecretStoreKind,
+    /// How the primary token was obtained
+    #[serde(default)]
+    pub auth_method: AuthMethod,
+    /// OAuth App client IDs for the device flow, keyed by web host
+    /// ("github.com", "ghes.example.com")
+    #[serde(default)]
+    pub oauth_client_ids: HashMap<String, String>,
+    /// Warn on the dashboard this many days before a token expires
+    #[serde(default = "default_token_expiry_warning_days")]
+    pub token_expiry_warning_days: u64,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    /// Hide to the tray instead of quitting when the main window is closed;
+    /// polling continues in the background
+    #[serde(default)]
+    pub close_to_tray: bool,
+    #[serde(default)]
+    pub adaptive_polling: AdaptivePolling,
+    /// Settings set in the app or by editing config.json, by key. Only these
+    /// override config.toml and the team config.
+    #[serde(default)]
+    pub set_in_app: BTreeSet<String>,
+    /// Turn rules, filters and sections from the TOML layers; never stored in
+    /// config.json
+    #[serde(skip)]
+    pub rules: Rules,
 }
 
 fn default_poll_interval() -> u64 {
     60_000
 }
 
+fn default_token_expiry_warning_days() -> u64 {
+    7
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             github_pat: String::new(),
             poll_interval_ms: default_poll_interval(),
+            sort: SortConfig::default(),
+            size_thresholds: SizeThresholds::default(),
+            bot_prs: BotPrMode::default(),
+            accounts: Vec::new(),
+            secret_store: SecretStoreKind::default(),
+            auth_method: AuthMethod::default(),
+            oauth_client_ids: HashMap::new(),
+            token_expiry_warning_days: default_token_expiry_warning_days(),
+            notifications: NotificationConfig::default(),
+            close_to_tray: false,
+            adaptive_polling: AdaptivePolling::default(),
+            set_in_app: BTreeSet::new(),
+            rules: Rules::default(),
         }
     }
 }
 
-fn get_config_path() -> Result<PathBuf, String> {
+impl AppConfig {
+    /// Accounts to fetch: the configured list, led by a github.com account
+    /// built from `github_pat` unless the list already has one. This keeps
+    /// the primary token working after a GHES account is added.
+    pub fn effective_accounts(&self) -> Vec<GitHubAccount> {
+        let mut accounts = Vec::new();
+        if !self.github_pat.is_empty() && !self.lists_github_com() {
+            accounts.push(GitHubAccount::github_com(self.github_pat.clone()));
+        }
+        accounts.extend(self.accounts.iter().cloned());
+        accounts
+    }
+
+    /// Whether `accounts` has a github.com entry, which replaces the primary
+    /// token.
+    pub fn lists_github_com(&self) -> bool {
+        self.accounts
+            .iter()
+            .any(|a| a.api_base_url.trim_end_matches('/') == GITHUB_API)
+    }
+
+    /// Secret-store key a new github.com token goes under: the listed
+    /// github.com account's when there is one, else the primary token's.
+    fn github_com_token_key(&self) -> String {
+        self.accounts
+            .iter()
+            .find(|a| a.api_base_url.trim_end_matches('/') == GITHUB_API)
+            .map(GitHubAccount::secret_key)
+            .unwrap_or_else(|| GITHUB_PAT_KEY.to_string())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitHubAccount {
+    /// Stable key for the account's token in the secret store, so renaming
+    /// the account keeps its token. Assigned when the account is saved.
+    #[serde(default)]
+    pub id: String,
+    pub label: String,
+    /// Only set in memory; on disk the token lives in the secret store
+    #[serde(default)]
+    pub token: String,
+    /// REST API root; "https://HOST/api/v3" for GitHub Enterprise Server
+    #[serde(default = "default_api_base_url")]
+    pub api_base_url: String,
+}
+
+fn default_api_base_url() -> String {
+    GITHUB_API.to_string()
+}
+
+impl GitHubAccount {
+    pub fn github_com(token: String) -> Self {
+        Self {
+            id: "github.com".to_string(),
+            label: "github.com".to_string(),
+            token,
+            api_base_url: default_api_base_url(),
+        }
+    }
+
+    /// Accounts added by hand to config.json have no id yet; their token is
+    /// keyed by label, as it was before ids existed.
+    fn secret_key(&self) -> String {
+        let id = if self.id.is_empty() {
+            &self.label
+        } else {
+            &self.id
+        };
+        format!("account:{}", id)
+    }
+}
+
+/// An id for a new account: its label, made unique among `accounts`.
+fn new_account_id(label: &str, accounts: &[GitHubAccount]) -> String {
+    let taken = |id: &str| accounts.iter().any(|a| a.id == id);
+    let mut id = label.to_string();
+    let mut n = 2;
+    while taken(&id) {
+        id = format!("{}-{}", label, n);
+        n += 1;
+    }
+    id
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMethod {
+    /// Personal Access Token pasted by the user
+    #[default]
+    Pat,
+    /// Token from the OAuth device flow
+    Oauth,
+}
+
+/// Secret-store key for the legacy single token.
+const GITHUB_PAT_KEY: &str = "github_pat";
+
+/// Bumped whenever the tokens in use may have changed. Tokens never appear in
+/// the config the poller compares, so this is how it tells a new token from
+/// an unrelated settings change.
+static TOKENS_VERSION: AtomicU64 = AtomicU64::new(0);
+
+pub fn tokens_version() -> u64 {
+    TOKENS_VERSION.load(Ordering::Relaxed)
+}
+
+fn tokens_changed() {
+    TOKENS_VERSION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// What the frontend receives: the config with every token blanked, plus
+/// whether one is stored.
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigView {
+    #[serde(flatten)]
+    pub config: AppConfig,
+    pub has_github_pat: bool,
+    /// Where the primary token comes from, when there is one
+    pub github_pat_source: Option<TokenSource>,
+    /// Where each account's token comes from, keyed by label
+    pub account_token_sources: HashMap<String, TokenSource>,
+    /// Set when the secret store couldn't be read, e.g. the encrypted file is
+    /// still locked
+    pub secret_store_error: Option<String>,
+    /// Set when config.json was unreadable and has been replaced by defaults
+    pub config_error: Option<String>,
+    /// Set when config.toml or the team config couldn't be applied
+    pub layer_error: Option<String>,
+}
+
+/// Event emitted to every window with the new `ConfigView` after a change.
+pub const CONFIG_CHANGED_EVENT: &str = "config-changed";
+
+/// Event emitted with an error message when a hand edit to config.json is
+/// rejected; the previous settings stay in effect.
+pub const CONFIG_ERROR_EVENT: &str = "config-error";
+
+/// Partial settings update for `update_config`. Omitted fields are left as
+/// they are.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct ConfigPatch {
+    /// Stored in the secret store, never in config.json
+    pub github_pat: Option<String>,
+    pub poll_interval_ms: Option<u64>,
+    pub sort: Option<SortConfig>,
+    pub size_thresholds: Option<SizeThresholds>,
+    pub bot_prs: Option<BotPrMode>,
+    pub oauth_client_ids: Option<HashMap<String, String>>,
+    pub token_expiry_warning_days: Option<u64>,
+    pub notifications: Option<NotificationConfig>,
+    pub close_to_tray: Option<bool>,
+    pub adaptive_polling: Option<AdaptivePolling>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FieldError {
+    /// Dotted path of the offending field, e.g. "size_thresholds.m"
+    pub field: String,
+    pub message: String,
+}
+
+/// Error returned by `update_config`. `fields` is empty unless the patch
+/// failed validation, in which case nothing was written.
+#[derive(Debug, Serialize)]
+pub struct ConfigUpdateError {
+    pub message: String,
+    pub fields: Vec<FieldError>,
+}
+
+impl From<String> for ConfigUpdateError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            fields: Vec::new(),
+        }
+    }
+}
+
+const MIN_POLL_INTERVAL_MS: u64 = 10_000;
+const MAX_POLL_INTERVAL_MS: u64 = 3_600_000;
+const MAX_TOKEN_EXPIRY_WARNING_DAYS: u64 = 365;
+const MAX_NOTIFICATIONS_PER_MINUTE: u32 = 60;
+const SORT_SECTIONS: &[&str] = &["my-prs", "review-requests"];
+
+impl ConfigPatch {
+    /// `AppConfig` keys of the settings the patch sets; the token isn't one.
+    fn settings_keys(&self) -> Vec<&'static str> {
+        let fields = [
+            ("poll_interval_ms", self.poll_interval_ms.is_some()),
+            ("sort", self.sort.is_some()),
+            ("size_thresholds", self.size_thresholds.is_some()),
+            ("bot_prs", self.bot_prs.is_some()),
+            ("oauth_client_ids", self.oauth_client_ids.is_some()),
+            (
+                "token_expiry_warning_days",
+                self.token_expiry_warning_days.is_some(),
+            ),
+            ("notifications", self.notifications.is_some()),
+            ("close_to_tray", self.close_to_tray.is_some()),
+            ("adaptive_polling", self.adaptive_polling.is_some()),
+        ];
+        fields
+            .into_iter()
+            .filter(|(_, set)| *set)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        let mut error = |field: &str, message: String| {
+            errors.push(FieldError {
+                field: field.to_string(),
+                message,
+            })
+        };
+
+        if let Some(ref pat) = self.github_pat {
+            if pat.trim().is_empty() {
+                error("github_pat", "Token must not be empty".to_string());
+            } else if pat.chars().any(char::is_whitespace) {
+                error("github_pat", "Token must not contain whitespace".to_string());
+            }
+        }
+
+        if let Some(interval) = self.poll_interval_ms {
+            if !(MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS).contains(&interval) {
+                error(
+                    "poll_interval_ms",
+                    format!(
+                        "Must be between {} and {} ms",
+                        MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS
+                    ),
+                );
+            }
+        }
+
+        if let Some(ref sort) = self.sort {
+            for section in sort.sections.keys() {
+                if !SORT_SECTIONS.contains(&section.as_str()) {
+                    error(
+                        &format!("sort.sections.{}", section),
+                        format!(
+                            "Unknown section; expected one of {}",
+                            SORT_SECTIONS.join(", ")
+                        ),
+                    );
+                }
+            }
+            if sort.label_priority.iter().any(|label| label.trim().is_empty()) {
+                error("sort.label_priority", "Labels must not be empty".to_string());
+            }
+        }
+
+        if let Some(ref t) = self.size_thresholds {
+            let ordered = [("xs", t.xs), ("s", t.s), ("m", t.m), ("l", t.l)];
+            if t.xs == 0 {
+                error("size_thresholds.xs", "Must be greater than 0".to_string());
+            }
+            for pair in ordered.windows(2) {
+                let ((lower_name, lower), (name, value)) = (pair[0], pair[1]);
+                if value <= lower {
+                    error(
+                        &format!("size_thresholds.{}", name),
+                        format!("Must be greater than {} ({})", lower_name, lower),
+                    );
+                }
+            }
+        }
+
+        if let Some(ref ids) = self.oauth_client_ids {
+            for (host, id) in ids {
+                if host.is_empty() || host.contains('/') {
+                    error(
+                        "oauth_client_ids",
+                        format!("\"{}\" is not a host name, e.g. \"github.com\"", host),
+                    );
+                }
+                if id.trim().is_empty() {
+                    error(
+                        &format!("oauth_client_ids.{}", host),
+                        "Client ID must not be empty".to_string(),
+                    );
+                }
+            }
+        }
+
+        if let Some(days) = self.token_expiry_warning_days {
+            if days > MAX_TOKEN_EXPIRY_WARNING_DAYS {
+                error(
+                    "token_expiry_warning_days",
+                    format!("Must be at most {}", MAX_TOKEN_EXPIRY_WARNING_DAYS),
+                );
+            }
+        }
+
+        if let Some(ref n) = self.notifications {
+            if !(1..=MAX_NOTIFICATIONS_PER_MINUTE).contains(&n.max_per_minute) {
+                error(
+                    "notifications.max_per_minute",
+                    format!("Must be between 1 and {}", MAX_NOTIFICATIONS_PER_MINUTE),
+                );
+            }
+            if let Some(repo) = n.muted_repos.iter().find(|r| r.split('/').count() != 2) {
+                error(
+                    "notifications.muted_repos",
+                    format!("\"{}\" is not \"owner/name\"", repo),
+                );
+            }
+        }
+
+        if let Some(ref a) = self.adaptive_polling {
+            let bounds = [
+                ("adaptive_polling.min_interval_ms", a.min_interval_ms),
+                ("adaptive_polling.max_interval_ms", a.max_interval_ms),
+            ];
+            for (field, interval) in bounds {
+                if !(MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS).contains(&interval) {
+                    error(
+                        field,
+                        format!(
+                            "Must be between {} and {} ms",
+                            MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS
+                        ),
+                    );
+                }
+            }
+            if a.max_interval_ms < a.min_interval_ms {
+                error(
+                    "adaptive_polling.max_interval_ms",
+                    format!("Must be at least min_interval_ms ({})", a.min_interval_ms),
+                );
+            }
+            if let Some(ref hours) = a.working_hours {
+                for (field, hour) in [("start_hour", hours.start_hour), ("end_hour", hours.end_hour)] {
+                    if hour > 23 {
+                        error(
+                            &format!("adaptive_polling.working_hours.{}", field),
+                            "Must be between 0 and 23".to_string(),
+                        );
+                    }
+                }
+                if hours.start_hour == hours.end_hour {
+                    error(
+                        "adaptive_polling.working_hours.end_hour",
+                        "Must differ from start_hour".to_string(),
+                    );
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+/// Check an account list for `save_accounts`. Labels key accounts throughout
+/// (the enrichment cache, token sources, single-PR refreshes), so they must
+/// be unique; "github.com" is the primary token's account.
+fn validate_accounts(accounts: &[GitHubAccount]) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let mut error = |field: String, message: String| errors.push(FieldError { field, message });
+    let primary_label = GitHubAccount::github_com(String::new()).label;
+
+    for (i, account) in accounts.iter().enumerate() {
+        let label = account.label.trim();
+        let on_github_com = account.api_base_url.trim_end_matches('/') == GITHUB_API;
+        if label.is_empty() {
+            error(
+                format!("accounts.{}.label", i),
+                "Label must not be empty".to_string(),
+            );
+        } else if accounts[..i].iter().any(|a| a.label.trim() == label) {
+            error(
+                format!("accounts.{}.label", i),
+                format!("\"{}\" is used by another account", label),
+            );
+        } else if label == primary_label && !on_github_com {
+            error(
+                format!("accounts.{}.label", i),
+                format!("\"{}\" is reserved for a github.com account", label),
+            );
+        }
+
+        let url_ok = reqwest::Url::parse(&account.api_base_url)
+            .is_ok_and(|url| matches!(url.scheme(), "https" | "http") && url.host().is_some());
+        if !url_ok {
+            error(
+                format!("accounts.{}.api_base_url", i),
+                format!(
+                    "\"{}\" is not an API URL, e.g. \"https://ghes.example.com/api/v3\"",
+                    account.api_base_url
+                ),
+            );
+        }
+
+        if account.token.chars().any(char::is_whitespace) {
+            error(
+                format!("accounts.{}.token", i),
+                "Token must not contain whitespace".to_string(),
+            );
+        }
+    }
+    errors
+}
+
+/// Validate a whole config with the same rules as `update_config`.
+pub fn validate_config(config: &AppConfig) -> Vec<FieldError> {
+    ConfigPatch {
+        github_pat: None,
+        poll_interval_ms: Some(config.poll_interval_ms),
+        sort: Some(config.sort.clone()),
+        size_thresholds: Some(config.size_thresholds.clone()),
+        bot_prs: Some(config.bot_prs),
+        oauth_client_ids: Some(config.oauth_client_ids.clone()),
+        token_expiry_warning_days: Some(config.token_expiry_warning_days),
+        notifications: Some(config.notifications.clone()),
+        close_to_tray: Some(config.close_to_tray),
+        adaptive_polling: Some(config.adaptive_polling.clone()),
+    }
+    .validate()
+}
+
+/// One ordering criterion. Keys are applied in order; later keys only break
+/// ties left by earlier ones.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortKey {
+    /// "my-turn" PRs before "their-turn" PRs
+    TurnStatus,
+    /// Most recently updated first
+    RecentlyUpdated,
+    /// Longest waiting on the current turn first (`turn_since`, or
+    /// `updated_at` when the turn start is unknown)
+    OldestWaiting,
+    /// Alphabetical by "owner/repo"
+    Repo,
+    /// PRs carrying a label listed earlier in `label_priority` first
+    LabelPriority,
+    /// Drafts after ready-for-review PRs
+    DraftLast,
+    /// Smallest diff (additions + deletions) first; unknown sizes last
+    Size,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SortConfig {
+    #[serde(default = "default_sort_keys")]
+    pub keys: Vec<SortKey>,
+    /// Per-section overrides keyed by section ("my-prs", "review-requests").
+    #[serde(default)]
+    pub sections: HashMap<String, Vec<SortKey>>,
+    /// Label names from highest to lowest priority (case-insensitive).
+    #[serde(default)]
+    pub label_priority: Vec<String>,
+}
+
+fn default_sort_keys() -> Vec<SortKey> {
+    vec![SortKey::TurnStatus, SortKey::RecentlyUpdated]
+}
+
+impl Default for SortConfig {
+    fn default() -> Self {
+        Self {
+            keys: default_sort_keys(),
+            sections: HashMap::new(),
+            label_priority: Vec::new(),
+        }
+    }
+}
+
+impl SortConfig {
+    /// Keys for a section, falling back to the global keys when the section
+    /// has no override.
+    pub fn keys_for(&self, section: &str) -> &[SortKey] {
+        self.sections
+            .get(section)
+            .map(|keys| keys.as_slice())
+            .unwrap_or(&self.keys)
+    }
+}
+
+/// Upper bounds (inclusive) on lines changed (additions + deletions) for each
+/// size bucket. Anything above `l` is XL.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SizeThresholds {
+    #[serde(default = "default_xs_threshold")]
+    pub xs: u64,
+    #[serde(default = "default_s_threshold")]
+    pub s: u64,
+    #[serde(default = "default_m_threshold")]
+    pub m: u64,
+    #[serde(default = "default_l_threshold")]
+    pub l: u64,
+}
+
+fn default_xs_threshold() -> u64 {
+    10
+}
+
+fn default_s_threshold() -> u64 {
+    100
+}
+
+fn default_m_threshold() -> u64 {
+    500
+}
+
+fn default_l_threshold() -> u64 {
+    1000
+}
+
+impl Default for SizeThresholds {
+    fn default() -> Self {
+        Self {
+            xs: default_xs_threshold(),
+            s: default_s_threshold(),
+            m: default_m_threshold(),
+            l: default_l_threshold(),
+        }
+    }
+}
+
+/// How review requests authored by bots (Dependabot, Renovate, ...) are shown.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum BotPrMode {
+    /// Mixed in with human PRs
+    #[default]
+    Show,
+    /// Dropped before enrichment
+    Hide,
+    /// Moved to a separate `botPrs` list
+    Group,
+    /// Kept in place but sorted after human PRs
+    Deprioritize,
+}
+
+/// Native notifications for dashboard changes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    /// Change kinds that notify. Approvals and change requests only notify
+    /// for the user's own PRs.
+    pub events: Vec<DashboardChangeKind>,
+    /// "owner/name" of repositories that never notify (case-insensitive)
+    pub muted_repos: Vec<String>,
+    /// Notifications beyond this in a minute are folded into one summary
+    pub max_per_minute: u32,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            events: vec![
+                DashboardChangeKind::BecameMyTurn,
+                DashboardChangeKind::NewReviewRequest,
+                DashboardChangeKind::NewApproval,
+                DashboardChangeKind::ChangesRequested,
+            ],
+            muted_repos: Vec::new(),
+            max_per_minute: 5,
+        }
+    }
+}
+
+/// Polling that speeds up while my PRs are active and slows down when idle,
+/// outside working hours, or as the rate limit runs low. Off by default, in
+/// which case `poll_interval_ms` is used as is.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AdaptivePolling {
+    pub enabled: bool,
+    /// Fastest interval, used while one of my PRs is active
+    pub min_interval_ms: u64,
+    /// Slowest interval, used outside working hours
+    pub max_interval_ms: u64,
+    /// Unset means every hour counts as working time
+    pub working_hours: Option<WorkingHours>,
+}
+
+impl Default for AdaptivePolling {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_interval_ms: 30_000,
+            max_interval_ms: 900_000,
+            working_hours: None,
+        }
+    }
+}
+
+/// Local hours, `start_hour` inclusive to `end_hour` exclusive. A start after
+/// the end spans midnight.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkingHours {
+    pub start_hour: u32,
+    pub end_hour: u32,
+    /// Treat Saturday and Sunday as outside working hours
+    #[serde(default = "default_weekdays_only")]
+    pub weekdays_only: bool,
+}
+
+fn default_weekdays_only() -> bool {
+    true
+}
+
+/// Overrides of the built-in turn rule for the author's own PRs, by GitHub's
+/// `mergeable_state`, e.g. `blocked = "my-turn"`. Unset states keep the
+/// built-in result.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct TurnRules {
+    pub clean: Option<TurnStatus>,
+    pub blocked: Option<TurnStatus>,
+    pub dirty: Option<TurnStatus>,
+    pub unstable: Option<TurnStatus>,
+    /// Any other state, or none reported
+    pub unknown: Option<TurnStatus>,
+}
+
+impl TurnRules {
+    /// The configured turn for a mergeable state, with the key it came from.
+    pub fn for_state(&self, state: &str) -> Option<(&'static str, TurnStatus)> {
+        let (key, status) = match state {
+            "clean" => ("clean", &self.clean),
+            "blocked" => ("blocked", &self.blocked),
+            "dirty" => ("dirty", &self.dirty),
+            "unstable" => ("unstable", &self.unstable),
+            _ => ("unknown", &self.unknown),
+        };
+        status.clone().map(|status| (key, status))
+    }
+}
+
+/// PRs to leave off the dashboard. Matching is case-insensitive.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Filters {
+    /// Hide every PR; mostly useful in a `[repo."org/name"]` table
+    pub hide: bool,
+    pub hide_drafts: bool,
+    pub hide_labels: Vec<String>,
+    pub hide_authors: Vec<String>,
+}
+
+/// Which dashboard sections PRs appear in.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Sections {
+    pub my_prs: bool,
+    pub review_requests: bool,
+}
+
+impl Default for Sections {
+    fn default() -> Self {
+        Self {
+            my_prs: true,
+            review_requests: true,
+        }
+    }
+}
+
+impl Sections {
+    pub fn shows(&self, section: &str) -> bool {
+        match section {
+            "my-prs" => self.my_prs,
+            _ => self.review_requests,
+        }
+    }
+}
+
+/// Rules set globally in the TOML config and overridable per repository.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepoRules {
+    pub turn_rules: TurnRules,
+    pub filters: Filters,
+    pub sections: Sections,
+}
+
+/// The global rules plus each `[repo."org/name"]` table already merged over
+/// them, keyed by lowercase "org/name".
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct Rules {
+    pub global: RepoRules,
+    pub repos: HashMap<String, RepoRules>,
+}
+
+impl Rules {
+    pub fn for_repo(&self, repo: &str) -> &RepoRules {
+        self.repos
+            .get(&repo.to_lowercase())
+            .unwrap_or(&self.global)
+    }
+}
+
+pub fn get_config_dir() -> Result<PathBuf, String> {
     let config_dir = dirs::config_dir()
         .ok_or_else(|| "Failed to determine config directory".to_string())?;
-    Ok(config_dir.join("gh-dash").join("config.json"))
+    Ok(config_dir.join("gh-dash"))
+}
+
+fn get_config_path() -> Result<PathBuf, String> {
+    Ok(get_config_dir()?.join("config.json"))
+}
+
+fn open_secret_store(kind: SecretStoreKind) -> Result<Box<dyn SecretStore>, String> {
+    secrets::open_store(kind, &get_config_dir()?)
 }
 
-#[tauri::command]
-pub fn get_config() -> Result<AppConfig, String> {
+/// Advisory lock on config.lock, held across each read-modify-write of
+/// config.json so two app instances, or a command racing a watcher reload,
+/// can't lose each other's updates. Released when dropped.
+struct ConfigLock {
+    _file: fs::File,
+}
+
+/// Take the config lock. Not reentrant: never take it twice on one thread.
+fn lock_config() -> Result<ConfigLock, String> {
+    let config_dir = get_config_dir()?;
+    fs::create_dir_all(&config_dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(config_dir.join("config.lock"))
+        .map_err(|e| format!("Failed to open config lock: {}", e))?;
+    file.lock()
+        .map_err(|e| format!("Failed to lock config: {}", e))?;
+    Ok(ConfigLock { _file: file })
+}
+
+fn write_config(_lock: &ConfigLock, config: &AppConfig) -> Result<(), String> {
     let config_path = get_config_path()?;
+    let mut config = config.clone();
+    config.version = CONFIG_VERSION;
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    secrets::write_private_file(&config_path, json.as_bytes())
+        .map_err(|e| format!("Failed to write config: {}", e))?;
+    remember_config(json, &config);
+    Ok(())
+}
 
-    if !config_path.exists() {
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config dir: {}", e))?;
+/// The last config.json contents that parsed and validated, and the config
+/// they produced. Lets `read_config` skip re-parsing an unchanged file, the
+/// watcher ignore the app's own writes, and a bad hand edit fall back to the
+/// settings already in effect.
+struct LoadedConfig {
+    contents: String,
+    config: AppConfig,
+}
+
+static LAST_GOOD_CONFIG: Mutex<Option<LoadedConfig>> = Mutex::new(None);
+
+fn remember_config(contents: String, config: &AppConfig) {
+    *LAST_GOOD_CONFIG.lock().unwrap_or_else(|e| e.into_inner()) = Some(LoadedConfig {
+        contents,
+        config: config.clone(),
+    });
+}
+
+/// The remembered config, if `contents` is what it was loaded from (or any
+/// contents when `None`).
+fn last_good_config(contents: Option<&str>) -> Option<AppConfig> {
+    LAST_GOOD_CONFIG
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .filter(|loaded| contents.is_none_or(|c| c == loaded.contents))
+        .map(|loaded| loaded.config.clone())
+}
+
+// ---------------------------------------------------------------------------
+// Migrations
+// ---------------------------------------------------------------------------
+
+/// `MIGRATIONS[n]` upgrades a version-n document to version n + 1. Steps work
+/// on raw JSON so they can rename or reshape fields the current `AppConfig`
+/// no longer knows about.
+const MIGRATIONS: &[fn(&mut Value)] = &[migrate_v0_to_v1, migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// Version 0 files predate the `version` field; the layout is otherwise the
+/// same as version 1.
+fn migrate_v0_to_v1(_config: &mut Value) {}
+
+/// Version 2 gives accounts an `id`. Tokens were stored under the label, so
+/// the label becomes the id and stored tokens stay where they are.
+fn migrate_v1_to_v2(config: &mut Value) {
+    let Some(accounts) = config.get_mut("accounts").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for account in accounts {
+        if let Some(label) = account.get("label").cloned() {
+            account["id"] = label;
         }
+    }
+}
+
+/// Version 3 records which settings were set in the app. Earlier files only
+/// tell that by a value differing from its default, so those count as set.
+fn migrate_v2_to_v3(config: &mut Value) {
+    let defaults = serde_json::to_value(AppConfig::default()).unwrap_or(Value::Null);
+    let set_in_app: Vec<Value> = layers::SETTINGS_KEYS
+        .iter()
+        .filter(|key| {
+            config
+                .get(**key)
+                .is_some_and(|value| Some(value) != defaults.get(**key))
+        })
+        .map(|key| Value::from(*key))
+        .collect();
+    config["set_in_app"] = Value::Array(set_in_app);
+}
+
+/// Bring a config document up to `CONFIG_VERSION`. Returns whether anything
+/// changed, so the upgraded file can be written back.
+fn migrate_config(doc: &mut Value) -> Result<bool, String> {
+    let version = doc.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "config.json is version {}, but this app only understands up to version {}. \
+             Update the app.",
+            version, CONFIG_VERSION
+        ));
+    }
+    for migrate in &MIGRATIONS[version as usize..] {
+        migrate(doc);
+    }
+    doc["version"] = Value::from(CONFIG_VERSION);
+    Ok(version < CONFIG_VERSION)
+}
+
+/// Why config.json was last replaced by defaults, reported through
+/// `ConfigView` for the rest of the session.
+static CONFIG_RECOVERY: Mutex<Option<String>> = Mutex::new(None);
+
+/// Move an unreadable config.json aside and start over with defaults, so a
+/// bad hand edit doesn't block startup. Tokens stay in the secret store.
+/// Runs once at startup; later reads report a bad file instead.
+pub fn recover_corrupt_config() -> Result<(), String> {
+    let lock = lock_config()?;
+    let config_path = get_config_path()?;
+    let Ok(contents) = fs::read_to_string(&config_path) else {
+        return Ok(());
+    };
+    let Err(ConfigParseError::Invalid(error)) = parse_config(&contents) else {
+        return Ok(());
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let backup_path = config_path.with_file_name(format!("config.json.corrupt-{}", timestamp));
+    fs::rename(&config_path, &backup_path)
+        .map_err(|e| format!("Failed to back up corrupt config: {}", e))?;
+
+    write_config(&lock, &AppConfig::default())?;
+    *CONFIG_RECOVERY.lock().unwrap_or_else(|e| e.into_inner()) = Some(format!(
+        "{}. Settings were reset; the old file was saved as {}.",
+        error,
+        backup_path.display()
+    ));
+    Ok(())
+}
+
+enum ConfigParseError {
+    /// Written by a newer version of the app; must not be overwritten
+    Unsupported(String),
+    Invalid(String),
+}
+
+/// Parse and migrate config.json contents. Returns whether a migration ran.
+fn parse_config(contents: &str) -> Result<(AppConfig, bool), ConfigParseError> {
+    let mut doc: Value = match serde_json::from_str(contents) {
+        Ok(doc @ Value::Object(_)) => doc,
+        Ok(_) => {
+            return Err(ConfigParseError::Invalid(
+                "Failed to parse config: expected a JSON object".to_string(),
+            ))
+        }
+        Err(e) => {
+            return Err(ConfigParseError::Invalid(format!(
+                "Failed to parse config: {}",
+                e
+            )))
+        }
+    };
+    let migrated = migrate_config(&mut doc).map_err(ConfigParseError::Unsupported)?;
+    let config = serde_json::from_value(doc)
+        .map_err(|e| ConfigParseError::Invalid(format!("Failed to parse config: {}", e)))?;
+    Ok((config, migrated))
+}
+
+pub fn describe_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Read config.json as stored on disk, creating it with defaults if missing.
+/// Older schema versions are migrated and written back. Tokens are not
+/// resolved; see `load_config`.
+///
+/// Once a valid config has been loaded, a bad edit (unparseable or failing
+/// validation) is ignored in favour of the last valid config, but settings
+/// can't be changed in the app until it is fixed. A file that was never
+/// valid is an error; `recover_corrupt_config` resets it at startup.
+pub fn read_config() -> Result<AppConfig, String> {
+    let lock = lock_config()?;
+    read_config_locked(&lock, ReadMode::LastGood)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReadMode {
+    /// Serve the last valid config in place of a bad edit
+    LastGood,
+    /// Report a bad edit, for callers about to write the config back, which
+    /// would otherwise replace the user's edit with the cached settings
+    Strict,
+}
+
+fn read_config_locked(lock: &ConfigLock, mode: ReadMode) -> Result<AppConfig, String> {
+    let config_path = get_config_path()?;
+
+    if !config_path.exists() {
         let default_config = AppConfig::default();
-        let json = serde_json::to_string_pretty(&default_config)
-            .map_err(|e| format!("Failed to serialize config: {}", e))?;
-        fs::write(&config_path, json)
-            .map_err(|e| format!("Failed to write config: {}", e))?;
+        write_config(lock, &default_config)?;
         return Ok(default_config);
     }
 
     let contents = fs::read_to_string(&config_path)
         .map_err(|e| format!("Failed to read config: {}", e))?;
-    serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to parse config: {}", e))
+    if let Some(config) = last_good_config(Some(&contents)) {
+        return Ok(config);
+    }
+    let fallback = |error: String| match mode {
+        ReadMode::LastGood => last_good_config(None).ok_or(error),
+        ReadMode::Strict => Err(format!(
+            "config.json has an invalid edit ({}). Fix or revert it before changing settings.",
+            error
+        )),
+    };
+
+    let (mut config, migrated) = match parse_config(&contents) {
+        Ok(parsed) => parsed,
+        Err(ConfigParseError::Unsupported(e)) => return Err(e),
+        Err(ConfigParseError::Invalid(e)) => return fallback(e),
+    };
+    // Files from before validation existed may hold out-of-range values;
+    // those are accepted at startup but not as a live edit
+    let errors = validate_config(&config);
+    if !errors.is_empty() && last_good_config(None).is_some() {
+        return fallback(describe_field_errors(&errors));
+    }
+    if migrated || record_hand_edits(&mut config) {
+        write_config(lock, &config)?;
+    } else {
+        remember_config(contents, &config);
+    }
+    Ok(config)
 }
 
-#[tauri::command]
-pub fn save_pat(pat: String) -> Result<AppConfig, String> {
-    let config_path = get_config_path()?;
-    let mut config = get_config()?;
-    config.github_pat = pat;
+// ---------------------------------------------------------------------------
+// Hot reload
+// ---------------------------------------------------------------------------
 
-    let json = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    fs::write(&config_path, json)
-        .map_err(|e| format!("Failed to write config: {}", e))?;
-    Ok(config)
+/// How long to wait after a change before reading config.json, so editors
+/// that save in several steps are read once, complete.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Settings changed by hand in config.json override the TOML files like ones
+/// changed in the app, so add them to `set_in_app`. Returns whether any were
+/// added, in which case the file should be written back so they still count
+/// after a restart.
+fn record_hand_edits(config: &mut AppConfig) -> bool {
+    let Some(previous) = last_good_config(None) else {
+        return false;
+    };
+    let mut added = false;
+    for key in layers::changed_settings(&previous, config) {
+        added |= config.set_in_app.insert(key.to_string());
+    }
+    added
 }
 
-#[tauri::command]
-pub fn save_poll_interval(interval_ms: u64) -> Result<AppConfig, String> {
+/// Watch config.json for edits made outside the app (by hand, or synced from
+/// dotfiles) and apply them live: valid edits are broadcast as
+/// `CONFIG_CHANGED_EVENT`, invalid ones as `CONFIG_ERROR_EVENT`. Edits to
+/// config.toml or the team file it names are re-layered and broadcast with
+/// any error in `layer_error`.
+pub fn watch_config(app: AppHandle) -> Result<(), String> {
+    // Make sure the directory exists before watching it; a bad config.json
+    // is not a reason to skip watching for its fix
+    let config_dir = get_config_dir()?;
+    fs::create_dir_all(&config_dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
     let config_path = get_config_path()?;
-    let mut config = get_config()?;
-    config.poll_interval_ms = interval_ms;
+    let toml_path = layers::user_config_path()?;
 
-    let json = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    fs::write(&config_path, json)
-        .map_err(|e| format!("Failed to write config: {}", e))?;
-    Ok(config)
+    let (tx, rx) = std::sync::mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| format!("Failed to watch config: {}", e))?;
+    // Watch the directory rather than the file: atomic saves replace the
+    // file, which would end a watch on the file itself
+    watcher
+        .watch(&config_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch config: {}", e))?;
+
+    std::thread::spawn(move || {
+        // The watcher stops when dropped, so it lives on this thread
+        let mut watcher = watcher;
+        let mut team_path = layers::team_config_path();
+        let mut team_dir = None;
+        watch_team_dir(
+            &mut watcher,
+            &config_dir,
+            team_path.as_deref(),
+            &mut team_dir,
+        );
+        let touches = |event: &notify::Result<notify::Event>, path: &Path| match event {
+            Ok(event) => {
+                !matches!(event.kind, EventKind::Access(_))
+                    && event.paths.iter().any(|p| p == path)
+            }
+            Err(_) => false,
+        };
+        while let Ok(event) = rx.recv() {
+            let touches_team = |event: &_| {
+                team_path
+                    .as_deref()
+                    .is_some_and(|path| touches(event, path))
+            };
+            let mut json_changed = touches(&event, &config_path);
+            let mut toml_changed = touches(&event, &toml_path) || touches_team(&event);
+            if !json_changed && !toml_changed {
+                continue;
+            }
+            std::thread::sleep(RELOAD_DEBOUNCE);
+            while let Ok(event) = rx.try_recv() {
+                json_changed |= touches(&event, &config_path);
+                toml_changed |= touches(&event, &toml_path) || touches_team(&event);
+            }
+            if json_changed {
+                reload_config(&app, &config_path);
+            }
+            if toml_changed {
+                // config.toml may name a different team file now
+                team_path = layers::team_config_path();
+                watch_team_dir(
+                    &mut watcher,
+                    &config_dir,
+                    team_path.as_deref(),
+                    &mut team_dir,
+                );
+                if let Ok(config) = read_config() {
+                    let _ = app.emit(CONFIG_CHANGED_EVENT, config_view(config));
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Watch the directory of the team file, unless it is the config directory,
+/// which is watched already. `watched` is the directory watched for the
+/// previous team file, if any.
+fn watch_team_dir(
+    watcher: &mut impl Watcher,
+    config_dir: &Path,
+    team_path: Option<&Path>,
+    watched: &mut Option<PathBuf>,
+) {
+    let dir = team_path
+        .and_then(Path::parent)
+        .filter(|dir| *dir != config_dir)
+        .map(Path::to_path_buf);
+    if dir == *watched {
+        return;
+    }
+    if let Some(old) = watched.take() {
+        let _ = watcher.unwatch(&old);
+    }
+    if let Some(dir) = dir {
+        match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            Ok(()) => *watched = Some(dir),
+            Err(e) => eprintln!("Not watching team config in {}: {}", dir.display(), e),
+        }
+    }
+}
+
+fn reload_config(app: &AppHandle, config_path: &Path) {
+    // Deleted: read_config recreates the defaults on next use
+    let Ok(contents) = fs::read_to_string(config_path) else {
+        return;
+    };
+    // Unchanged, or the app's own write
+    if last_good_config(Some(&contents)).is_some() {
+        return;
+    }
+
+    let result = match parse_config(&contents) {
+        Ok((config, _)) => {
+            let errors = validate_config(&config);
+            if errors.is_empty() {
+                Ok(config)
+            } else {
+                Err(describe_field_errors(&errors))
+            }
+        }
+        Err(ConfigParseError::Unsupported(e) | ConfigParseError::Invalid(e)) => Err(e),
+    };
+
+    match result {
+        Ok(mut config) => {
+            if record_hand_edits(&mut config) {
+                let saved = lock_config().and_then(|lock| write_config(&lock, &config));
+                if let Err(e) = saved {
+                    eprintln!("Failed to record edited settings in config.json: {}", e);
+                    remember_config(contents, &config);
+                }
+            } else {
+                remember_config(contents, &config);
+            }
+            credentials::forget_discovered_tokens();
+            let _ = app.emit(CONFIG_CHANGED_EVENT, config_view(config));
+        }
+        Err(e) => {
+            let message = format!("Ignored invalid edit to config.json: {}", e);
+            let _ = app.emit(CONFIG_ERROR_EVENT, message);
+        }
+    }
+}
+
+fn has_plaintext_secrets(config: &AppConfig) -> bool {
+    !config.github_pat.is_empty() || config.accounts.iter().any(|a| !a.token.is_empty())
+}
+
+/// Move plaintext tokens left in config.json by older versions into the
+/// secret store, then rewrite the file without them. Tokens stay in place if
+/// the store rejects them, so nothing is lost.
+fn migrate_plaintext_secrets(config: &AppConfig) -> Result<(), String> {
+    if !has_plaintext_secrets(config) {
+        return Ok(());
+    }
+
+    // Re-read under the lock: another instance may have migrated already
+    let lock = lock_config()?;
+    let mut current = read_config_locked(&lock, ReadMode::Strict)?;
+    if !has_plaintext_secrets(&current) {
+        return Ok(());
+    }
+    let store = open_secret_store(current.secret_store)?;
+    if !current.github_pat.is_empty() {
+        store.set(GITHUB_PAT_KEY, &current.github_pat)?;
+    }
+    for account in &current.accounts {
+        if !account.token.is_empty() {
+            store.set(&account.secret_key(), &account.token)?;
+        }
+    }
+
+    strip_secrets(&mut current);
+    write_config(&lock, &current)
+}
+
+fn strip_secrets(config: &mut AppConfig) {
+    config.github_pat.clear();
+    for account in &mut config.accounts {
+        account.token.clear();
+    }
+}
+
+/// Where each token filled in by `resolve_secrets` came from.
+#[derive(Debug, Default)]
+struct TokenSources {
+    github_pat: Option<TokenSource>,
+    accounts: HashMap<String, TokenSource>,
+    /// Set when the secret store couldn't be read; discovery still ran
+    store_error: Option<String>,
+}
+
+impl TokenSources {
+    /// The store error, if it left a token the config needs unresolved.
+    fn blocking_error(&self, config: &AppConfig) -> Option<&String> {
+        let missing = if config.accounts.is_empty() {
+            config.github_pat.is_empty()
+        } else {
+            config.accounts.iter().any(|a| a.token.is_empty())
+        };
+        self.store_error.as_ref().filter(|_| missing)
+    }
+}
+
+/// Read each missing token from the secret store. Stops at the first store
+/// error, leaving the remaining tokens empty.
+fn read_stored_secrets(config: &mut AppConfig) -> Result<(), String> {
+    let needs_store = config.github_pat.is_empty()
+        || config.accounts.iter().any(|a| a.token.is_empty());
+    if !needs_store {
+        return Ok(());
+    }
+    let store = open_secret_store(config.secret_store)?;
+    if config.github_pat.is_empty() {
+        config.github_pat = store.get(GITHUB_PAT_KEY)?.unwrap_or_default();
+    }
+    for account in &mut config.accounts {
+        if account.token.is_empty() {
+            account.token = store.get(&account.secret_key())?.unwrap_or_default();
+        }
+    }
+    Ok(())
+}
+
+/// Fill in tokens from the secret store, falling back to tokens discovered
+/// in the environment or the GitHub CLI, also when the store is locked or
+/// unavailable. Plaintext tokens that failed to migrate are kept as-is.
+fn resolve_secrets(config: &mut AppConfig) -> TokenSources {
+    let mut sources = TokenSources {
+        store_error: read_stored_secrets(config).err(),
+        ..TokenSources::default()
+    };
+
+    if !config.github_pat.is_empty() {
+        sources.github_pat = Some(TokenSource::Stored);
+    } else if !config.lists_github_com() {
+        // The primary token is only used when no github.com account is listed
+        if let Some((token, source)) = credentials::discover_token(GITHUB_API) {
+            config.github_pat = token;
+            sources.github_pat = Some(source);
+        }
+    }
+    for account in &mut config.accounts {
+        let source = if !account.token.is_empty() {
+            Some(TokenSource::Stored)
+        } else {
+            credentials::discover_token(&account.api_base_url).map(|(token, source)| {
+                account.token = token;
+                source
+            })
+        };
+        if let Some(source) = source {
+            sources.accounts.insert(account.label.clone(), source);
+        }
+    }
+    sources
+}
+
+/// Config with tokens resolved from the secret store and the TOML layers
+/// applied, for backend use only.
+pub fn load_config() -> Result<AppConfig, String> {
+    let mut config = read_config()?;
+    // A failed migration leaves the plaintext token usable; resolve_secrets
+    // surfaces any store problem on the next line.
+    let _ = migrate_plaintext_secrets(&config);
+    let sources = resolve_secrets(&mut config);
+    if let Some(e) = sources.blocking_error(&config) {
+        return Err(e.clone());
+    }
+    Ok(layers::apply_layers(config).config)
+}
+
+/// Config with the TOML layers applied but tokens left unresolved.
+pub fn effective_config() -> Result<AppConfig, String> {
+    Ok(layers::apply_layers(read_config()?).config)
+}
+
+fn config_view(mut config: AppConfig) -> ConfigView {
+    let migrate_error = migrate_plaintext_secrets(&config).err();
+    let mut sources = resolve_secrets(&mut config);
+    let secret_store_error = migrate_error.or(sources.store_error.take());
+    let has_github_pat = !config.github_pat.is_empty();
+    // Layered after the secret migration above, which writes config.json
+    let layered = layers::apply_layers(config);
+    let layer_error = layered.error();
+    let mut config = layered.config;
+    strip_secrets(&mut config);
+    ConfigView {
+        config,
+        has_github_pat,
+        github_pat_source: sources.github_pat,
+        account_token_sources: sources.accounts,
+        secret_store_error,
+        config_error: CONFIG_RECOVERY
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone(),
+        layer_error,
+    }
+}
+
+// The config commands resolve tokens, which can mean keyring calls, key
+// derivation for the encrypted store or running `gh auth token`, so they run
+// off the main thread to keep the UI responsive.
+#[tauri::command(async)]
+pub fn get_config() -> Result<ConfigView, String> {
+    Ok(config_view(read_config()?))
+}
+
+/// Validate and apply a partial settings update with a single write, then
+/// notify every window through `CONFIG_CHANGED_EVENT`.
+#[tauri::command(async)]
+pub fn update_config(
+    app: AppHandle,
+    patch: ConfigPatch,
+) -> Result<ConfigView, ConfigUpdateError> {
+    let errors = patch.validate();
+    if !errors.is_empty() {
+        return Err(ConfigUpdateError {
+            message: format!("Invalid settings: {}", describe_field_errors(&errors)),
+            fields: errors,
+        });
+    }
+    let patch_keys = patch.settings_keys();
+
+    let lock = lock_config()?;
+    let mut config = read_config_locked(&lock, ReadMode::Strict)?;
+    if let Some(pat) = patch.github_pat {
+        open_secret_store(config.secret_store)?.set(&config.github_com_token_key(), pat.trim())?;
+        config.github_pat.clear();
+        config.auth_method = AuthMethod::Pat;
+        // The cached dashboard may belong to another user
+        cache::clear();
+        tokens_changed();
+    }
+    if let Some(poll_interval_ms) = patch.poll_interval_ms {
+        config.poll_interval_ms = poll_interval_ms;
+    }
+    if let Some(sort) = patch.sort {
+        config.sort = sort;
+    }
+    if let Some(size_thresholds) = patch.size_thresholds {
+        config.size_thresholds = size_thresholds;
+    }
+    if let Some(bot_prs) = patch.bot_prs {
+        config.bot_prs = bot_prs;
+    }
+    if let Some(oauth_client_ids) = patch.oauth_client_ids {
+        config.oauth_client_ids = oauth_client_ids;
+    }
+    if let Some(days) = patch.token_expiry_warning_days {
+        config.token_expiry_warning_days = days;
+    }
+    if let Some(notifications) = patch.notifications {
+        config.notifications = notifications;
+    }
+    if let Some(close_to_tray) = patch.close_to_tray {
+        config.close_to_tray = close_to_tray;
+    }
+    if let Some(adaptive_polling) = patch.adaptive_polling {
+        config.adaptive_polling = adaptive_polling;
+    }
+    config
+        .set_in_app
+        .extend(patch_keys.into_iter().map(String::from));
+    write_config(&lock, &config)?;
+    drop(lock);
+
+    Ok(broadcast_config(&app, config))
+}
+
+/// Build the view of a config just changed through a command and send it as
+/// `CONFIG_CHANGED_EVENT`, so the poller and other windows pick it up. The
+/// change is already saved, so a failed emit doesn't fail the command.
+fn broadcast_config(app: &AppHandle, config: AppConfig) -> ConfigView {
+    credentials::forget_discovered_tokens();
+    let view = config_view(config);
+    let _ = app.emit(CONFIG_CHANGED_EVENT, &view);
+    view
+}
+
+/// Store a token obtained through the OAuth device flow. github.com tokens
+/// become the primary token, or the listed github.com account's; GHES tokens update or add the account for that
+/// API base URL, labelled with the host.
+pub fn store_oauth_token(
+    app: &AppHandle,
+    host: &str,
+    api_base_url: &str,
+    token: String,
+) -> Result<ConfigView, String> {
+    let lock = lock_config()?;
+    let mut config = read_config_locked(&lock, ReadMode::Strict)?;
+    let store = open_secret_store(config.secret_store)?;
+
+    let api_base_url = api_base_url.trim_end_matches('/');
+    if api_base_url == GITHUB_API {
+        store.set(&config.github_com_token_key(), &token)?;
+        config.github_pat.clear();
+        config.auth_method = AuthMethod::Oauth;
+    } else {
+        let account = match config
+            .accounts
+            .iter_mut()
+            .find(|a| a.api_base_url.trim_end_matches('/') == api_base_url)
+        {
+            Some(account) => account,
+            None => {
+                config.accounts.push(GitHubAccount {
+                    id: new_account_id(host, &config.accounts),
+                    label: host.to_string(),
+                    token: String::new(),
+                    api_base_url: api_base_url.to_string(),
+                });
+                config.accounts.last_mut().expect("account was just pushed")
+            }
+        };
+        store.set(&account.secret_key(), &token)?;
+        account.token.clear();
+    }
+    cache::clear();
+    tokens_changed();
+
+    write_config(&lock, &config)?;
+    drop(lock);
+    Ok(broadcast_config(app, config))
+}
+
+/// Replace the account list. Accounts sent with an empty token keep the token
+/// already stored under their id; accounts without an id are new and get one.
+/// Tokens of accounts no longer in the list are deleted.
+#[tauri::command(async)]
+pub fn save_accounts(
+    app: AppHandle,
+    accounts: Vec<GitHubAccount>,
+) -> Result<ConfigView, ConfigUpdateError> {
+    let errors = validate_accounts(&accounts);
+    if !errors.is_empty() {
+        return Err(ConfigUpdateError {
+            message: format!("Invalid accounts: {}", describe_field_errors(&errors)),
+            fields: errors,
+        });
+    }
+
+    let lock = lock_config()?;
+    let mut config = read_config_locked(&lock, ReadMode::Strict)?;
+    let store = open_secret_store(config.secret_store)?;
+    let mut accounts = accounts;
+    for account in &mut accounts {
+        account.label = account.label.trim().to_string();
+    }
+    for i in 0..accounts.len() {
+        if accounts[i].id.is_empty() {
+            accounts[i].id = new_account_id(&accounts[i].label, &accounts);
+        }
+    }
+    for account in &mut accounts {
+        if !account.token.is_empty() {
+            store.set(&account.secret_key(), &account.token)?;
+            account.token.clear();
+        }
+    }
+    let removed: Vec<String> = config
+        .accounts
+        .iter()
+        .map(GitHubAccount::secret_key)
+        .filter(|key| !accounts.iter().any(|a| a.secret_key() == *key))
+        .collect();
+    config.accounts = accounts;
+    cache::clear();
+    tokens_changed();
+    write_config(&lock, &config)?;
+    drop(lock);
+
+    // Only once config.json no longer lists them, so a failed write keeps
+    // every account usable
+    for key in &removed {
+        if let Err(e) = store.delete(key) {
+            eprintln!("Failed to delete the token for {}: {}", key, e);
+        }
+    }
+    Ok(broadcast_config(&app, config))
+}
+
+/// Unlock the encrypted-file store for this session.
+#[tauri::command(async)]
+pub fn unlock_secret_store(app: AppHandle, passphrase: String) -> Result<ConfigView, String> {
+    secrets::set_passphrase(passphrase);
+    let config = read_config()?;
+    // Reading a key verifies the passphrase against the existing file
+    open_secret_store(config.secret_store)?.get(GITHUB_PAT_KEY)?;
+    tokens_changed();
+    Ok(broadcast_config(&app, config))
+}
+
+/// Switch secret-store backends, moving every stored token across. Tokens
+/// discovered outside the app are left where they are. A passphrase is
+/// required when switching to the encrypted file. Also reachable from the
+/// setup screen, for systems where the keyring can't store the first token.
+#[tauri::command(async)]
+pub fn set_secret_store(
+    app: AppHandle,
+    kind: SecretStoreKind,
+    passphrase: Option<String>,
+) -> Result<ConfigView, String> {
+    let config = read_config()?;
+    if kind == config.secret_store {
+        return Ok(config_view(config));
+    }
+    let _ = migrate_plaintext_secrets(&config);
+    let lock = lock_config()?;
+    let mut config = read_config_locked(&lock, ReadMode::Strict)?;
+    if kind == config.secret_store {
+        drop(lock);
+        return Ok(config_view(config));
+    }
+    // An unreadable old store (no keyring daemon, say) is no reason to refuse:
+    // switching away from it is how the user gets a working one. Whatever
+    // it did return is moved.
+    let sources = resolve_secrets(&mut config);
+    if let Some(passphrase) = passphrase {
+        secrets::set_passphrase(passphrase);
+    }
+
+    let mut moves = Vec::new();
+    if sources.github_pat == Some(TokenSource::Stored) {
+        moves.push((GITHUB_PAT_KEY.to_string(), config.github_pat.clone()));
+    }
+    for account in &config.accounts {
+        if sources.accounts.get(&account.label) == Some(&TokenSource::Stored) {
+            moves.push((account.secret_key(), account.token.clone()));
+        }
+    }
+    // Copy everything before switching, so a failed write leaves config.json
+    // on the old store with every token still in it
+    let new_store = open_secret_store(kind)?;
+    for (key, token) in &moves {
+        new_store.set(key, token)?;
+    }
+
+    let old_kind = config.secret_store;
+    config.secret_store = kind;
+    tokens_changed();
+    strip_secrets(&mut config);
+    write_config(&lock, &config)?;
+    drop(lock);
+
+    // The tokens are safe in the new store now; leftovers in the old one are
+    // only untidy
+    if !moves.is_empty() {
+        match open_secret_store(old_kind) {
+            Ok(old_store) => {
+                for (key, _) in &moves {
+                    if let Err(e) = old_store.delete(key) {
+                        eprintln!("Failed to delete {} from the old secret store: {}", key, e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to open the old secret store: {}", e),
+        }
+    }
+    Ok(broadcast_config(&app, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(doc: Value) -> (AppConfig, bool) {
+        match parse_config(&doc.to_string()) {
+            Ok(parsed) => parsed,
+            Err(ConfigParseError::Unsupported(e) | ConfigParseError::Invalid(e)) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn migrates_version_0_to_current() {
+        let (config, migrated) = parse(serde_json::json!({
+            "github_pat": "",
+            "poll_interval_ms": 30_000,
+            "accounts": [
+                { "label": "work", "api_base_url": "https://ghes.example.com/api/v3" }
+            ]
+        }));
+        assert!(migrated);
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.accounts[0].id, "work");
+        assert_eq!(config.poll_interval_ms, 30_000);
+        assert_eq!(
+            config.set_in_app.iter().collect::<Vec<_>>(),
+            ["poll_interval_ms"]
+        );
+    }
+
+    #[test]
+    fn version_2_ids_are_kept() {
+        let (config, migrated) = parse(serde_json::json!({
+            "version": 2,
+            "accounts": [{ "id": "work-2", "label": "work" }]
+        }));
+        assert!(migrated);
+        assert_eq!(config.accounts[0].id, "work-2");
+        assert!(config.set_in_app.is_empty());
+    }
+
+    #[test]
+    fn settings_at_their_default_are_not_marked_set() {
+        let defaults = serde_json::to_value(AppConfig::default()).unwrap();
+        let mut doc = defaults.clone();
+        doc["version"] = Value::from(2);
+        doc["bot_prs"] = defaults["bot_prs"].clone();
+        doc["close_to_tray"] = Value::from(true);
+        let (config, _) = parse(doc);
+        assert_eq!(
+            config.set_in_app.iter().collect::<Vec<_>>(),
+            ["close_to_tray"]
+        );
+    }
+
+    #[test]
+    fn current_version_is_not_migrated() {
+        let (config, migrated) = parse(serde_json::json!({
+            "version": CONFIG_VERSION,
+            "set_in_app": ["sort"]
+        }));
+        assert!(!migrated);
+        assert!(config.set_in_app.contains("sort"));
+    }
+
+    #[test]
+    fn newer_version_is_refused() {
+        let doc = serde_json::json!({ "version": CONFIG_VERSION + 1 });
+        assert!(matches!(
+            parse_config(&doc.to_string()),
+            Err(ConfigParseError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn non_object_is_invalid() {
+        assert!(matches!(
+            parse_config("[]"),
+            Err(ConfigParseError::Invalid(_))
+        ));
+    }
+
+    fn ghes_account(label: &str) -> GitHubAccount {
+        GitHubAccount {
+            id: label.to_string(),
+            label: label.to_string(),
+            token: "ghes-token".to_string(),
+            api_base_url: "https://ghes.example.com/api/v3".to_string(),
+        }
+    }
+
+    #[test]
+    fn primary_token_joins_ghes_accounts() {
+        let config = AppConfig {
+            github_pat: "primary".to_string(),
+            accounts: vec![ghes_account("ghes")],
+            ..AppConfig::default()
+        };
+        let labels: Vec<String> = config
+            .effective_accounts()
+            .into_iter()
+            .map(|a| a.label)
+            .collect();
+        assert_eq!(labels, ["github.com", "ghes"]);
+    }
+
+    #[test]
+    fn listed_github_com_account_replaces_primary_token() {
+        let config = AppConfig {
+            github_pat: "primary".to_string(),
+            accounts: vec![GitHubAccount {
+                label: "personal".to_string(),
+                ..GitHubAccount::github_com("listed".to_string())
+            }],
+            ..AppConfig::default()
+        };
+        let accounts = config.effective_accounts();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].token, "listed");
+    }
 }
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+
+use crate::oauth::{host_of, web_base_url};
+
+/// Where the token in use for an account came from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenSource {
+    /// Saved by the app in its secret store
+    Stored,
+    /// GH_DASH_TOKEN, for every host
+    GhDashToken,
+    /// GH_TOKEN (github.com only)
+    GhToken,
+    /// GITHUB_TOKEN (github.com only)
+    GithubToken,
+    /// GH_ENTERPRISE_TOKEN (GitHub Enterprise Server only)
+    GhEnterpriseToken,
+    /// GITHUB_ENTERPRISE_TOKEN (GitHub Enterprise Server only)
+    GithubEnterpriseToken,
+    /// The GitHub CLI's login, from hosts.yml or `gh auth token`
+    GhCli,
+}
+
+/// Environment variables checked for github.com, in order.
+const GITHUB_COM_ENV: &[(&str, TokenSource)] = &[
+    ("GH_DASH_TOKEN", TokenSource::GhDashToken),
+    ("GH_TOKEN", TokenSource::GhToken),
+    ("GITHUB_TOKEN", TokenSource::GithubToken),
+];
+
+/// Environment variables checked for Enterprise Server hosts, in order. After
+/// the app's own variable, the same ones the GitHub CLI reads.
+const ENTERPRISE_ENV: &[(&str, TokenSource)] = &[
+    ("GH_DASH_TOKEN", TokenSource::GhDashToken),
+    ("GH_ENTERPRISE_TOKEN", TokenSource::GhEnterpriseToken),
+    ("GITHUB_ENTERPRISE_TOKEN", TokenSource::GithubEnterpriseToken),
+];
+
+/// One host entry in the GitHub CLI's hosts.yml. Recent CLI versions keep the
+/// token in the OS keyring instead, leaving `oauth_token` out.
+#[derive(Debug, Deserialize)]
+struct GhHostEntry {
+    oauth_token: Option<String>,
+}
+
+type Discovered = Option<(String, TokenSource)>;
+
+/// Discovery results by host, including hosts where nothing was found, so
+/// `gh auth token` isn't run on every config read.
+static DISCOVERED: Mutex<Option<HashMap<String, Discovered>>> = Mutex::new(None);
+
+/// Find a token for the GitHub instance at `api_base_url` outside the app:
+/// environment variables first, then the GitHub CLI's login for that host.
+/// Discovered tokens are only ever held in memory, and cached until
+/// `forget_discovered_tokens`.
+pub fn discover_token(api_base_url: &str) -> Discovered {
+    let web_url = web_base_url(api_base_url);
+    let host = host_of(&web_url).to_lowercase();
+    if let Some(found) = DISCOVERED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(&host))
+    {
+        return found.clone();
+    }
+    let found = discover_uncached(&host);
+    DISCOVERED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(host, found.clone());
+    found
+}
+
+/// Look for tokens again on next use, after the config changed or GitHub
+/// rejected a token.
+pub fn forget_discovered_tokens() {
+    *DISCOVERED.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn discover_uncached(host: &str) -> Discovered {
+    let env_vars = if host == "github.com" {
+        GITHUB_COM_ENV
+    } else {
+        ENTERPRISE_ENV
+    };
+
+    env_vars
+        .iter()
+        .find_map(|(name, source)| {
+            std::env::var(name)
+                .ok()
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty())
+                .map(|token| (token, *source))
+        })
+        .or_else(|| gh_cli_token(host).map(|token| (token, TokenSource::GhCli)))
+}
+
+/// The GitHub CLI's config directory, resolved the way the CLI does.
+fn gh_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("GH_CONFIG_DIR").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("gh"));
+    }
+    if cfg!(windows) {
+        if let Some(dir) = std::env::var_os("AppData") {
+            return Some(PathBuf::from(dir).join("GitHub CLI"));
+        }
+    }
+    dirs::home_dir().map(|home| home.join(".config").join("gh"))
+}
+
+fn gh_cli_token(host: &str) -> Option<String> {
+    hosts_yml_token(host).or_else(|| gh_auth_token(host))
+}
+
+fn hosts_yml_token(host: &str) -> Option<String> {
+    let contents = fs::read_to_string(gh_config_dir()?.join("hosts.yml")).ok()?;
+    let hosts: HashMap<String, GhHostEntry> = serde_yaml_ng::from_str(&contents).ok()?;
+    hosts
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(host))?
+        .1
+        .oauth_token
+        .filter(|token| !token.is_empty())
+}
+
+/// Ask the GitHub CLI directly, for logins stored in the OS keyring.
+fn gh_auth_token(host: &str) -> Option<String> {
+    let output = Command::new("gh")
+        .args(["auth", "token", "--hostname", host])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let token = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
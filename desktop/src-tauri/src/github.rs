@@ -1,8 +1,17 @@
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use futures::FutureExt;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-const GITHUB_API: &str = "https://api.github.com";
+use crate::changes::DashboardChange;
+use crate::config::{
+    self, BotPrMode, Rules, SizeThresholds, SortConfig, SortKey, TurnRules, GITHUB_API,
+};
+use crate::credentials;
 
 // ---------------------------------------------------------------------------
 // GitHub API response types (Deserialize only — inbound from GitHub)
@@ -14,6 +23,17 @@ pub struct GitHubUser {
     pub login: String,
     pub avatar_url: String,
     pub id: u64,
+    /// "User", "Bot" or "Organization"
+    #[serde(rename = "type", default)]
+    pub user_type: String,
+}
+
+impl GitHubUser {
+    /// GitHub Apps (Dependabot, Renovate, Copilot, ...) have type "Bot" and a
+    /// "[bot]" login suffix; check both since some payloads omit the type.
+    pub fn is_bot(&self) -> bool {
+        self.user_type == "Bot" || self.login.ends_with("[bot]")
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -50,6 +70,8 @@ pub struct GitHubRequestedReviewersResponse {
 pub struct GitHubPullRequest {
     pub url: String,
     pub html_url: String,
+    #[serde(default)]
+    pub merged_at: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -90,10 +112,68 @@ pub struct GitHubSearchResponse {
 pub struct GitHubPullDetail {
     pub mergeable: Option<bool>,
     pub mergeable_state: Option<String>,
+    #[serde(default)]
+    pub additions: u64,
+    #[serde(default)]
+    pub deletions: u64,
+    #[serde(default)]
+    pub changed_files: u64,
+    #[serde(default)]
+    pub commits: u64,
+    pub head: Option<GitHubPullHead>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GitHubPullHead {
+    pub sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitHubCheckRun {
+    /// "queued", "in_progress" or "completed"
+    pub status: String,
+    pub conclusion: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitHubCheckRunsResponse {
+    pub check_runs: Vec<GitHubCheckRun>,
+}
+
+/// Commit statuses reported through the older Statuses API.
+#[derive(Debug, Deserialize)]
+pub struct GitHubCombinedStatus {
+    /// "success", "pending" or "failure"
+    pub state: String,
+    pub total_count: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GitHubGitActor {
+    pub date: Option<String>,
+}
+
+/// One entry of the issue timeline. Only the fields used by the turn timing
+/// are deserialized; their presence depends on the event type.
+#[derive(Debug, Deserialize, Clone)]
+pub struct GitHubTimelineEvent {
+    pub event: Option<String>,
+    pub created_at: Option<String>,
+    /// Set on "reviewed" events
+    pub submitted_at: Option<String>,
+    /// Set on "reviewed" events
+    pub user: Option<GitHubUser>,
+    /// Set on "review_requested" events
+    pub requested_reviewer: Option<GitHubUser>,
+    /// Set on "review_requested" events
+    pub requested_team: Option<GitHubTeam>,
+    /// Set on "committed" events
+    pub committer: Option<GitHubGitActor>,
 }
 
 // ---------------------------------------------------------------------------
-// Dashboard types (Serialize — outbound to the frontend)
+// Dashboard types (Serialize — outbound to the frontend; Deserialize so the
+// frontend can hand a response back for re-sorting)
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -103,7 +183,7 @@ pub enum TurnStatus {
     TheirTurn,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum CheckResult {
     MyTurn,
@@ -111,14 +191,14 @@ pub enum CheckResult {
     Skip,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TurnDebugCheck {
     pub label: String,
     pub value: String,
     pub result: CheckResult,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TurnDebugInfo {
     pub section: String,
@@ -126,21 +206,78 @@ pub struct TurnDebugInfo {
     pub deciding_check: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DashboardAuthor {
     pub login: String,
     pub avatar_url: String,
+    #[serde(default)]
+    pub is_bot: bool,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DashboardLabel {
     pub name: String,
     pub color: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReviewerState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardReviewer {
+    /// User login, or the team name for team entries
+    pub login: String,
+    pub avatar_url: Option<String>,
+    /// Set for team review requests
+    pub team_slug: Option<String>,
+    /// Latest effective review state; COMMENTED doesn't clear an earlier
+    /// APPROVED or CHANGES_REQUESTED
+    pub state: ReviewerState,
+    pub submitted_at: Option<String>,
+    /// Reviewer has submitted a review and has been requested again since
+    pub requested_again: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SizeBucket {
+    XS,
+    S,
+    M,
+    L,
+    XL,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardPrSize {
+    pub additions: u64,
+    pub deletions: u64,
+    pub changed_files: u64,
+    pub commits: u64,
+    pub bucket: SizeBucket,
+}
+
+/// Combined result of the head commit's check runs and commit statuses.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CiStatus {
+    Success,
+    Pending,
+    Failure,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DashboardPR {
     pub id: u64,
@@ -148,23 +285,136 @@ pub struct DashboardPR {
     pub title: String,
     pub url: String,
     pub repo: String,
+    /// Label of the account this PR was fetched with
+    pub account: String,
     pub author: DashboardAuthor,
     pub turn_status: TurnStatus,
     pub turn_debug_info: Option<TurnDebugInfo>,
     pub is_draft: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// When the current turn began (ISO-8601), derived from the timeline.
+    pub turn_since: Option<String>,
+    /// Human-readable time since `turn_since` at fetch time, e.g. "2d 4h".
+    pub waiting_for: Option<String>,
     pub labels: Vec<DashboardLabel>,
+    pub reviewers: Vec<DashboardReviewer>,
+    /// Derived from `reviewers`, e.g. "2 approved, 1 pending (1 team)"
     pub review_summary: String,
+    /// Diff statistics; `None` when the pull detail was unavailable.
+    pub size: Option<DashboardPrSize>,
+    /// GitHub's `mergeable_state`, e.g. "clean", "blocked" or "dirty"
+    #[serde(default)]
+    pub mergeable_state: Option<String>,
+    /// `None` when the head commit has no checks or they couldn't be read
+    #[serde(default)]
+    pub ci_status: Option<CiStatus>,
+    /// Built from the search result alone while enrichment is running; the
+    /// turn status is a guess and reviewers, size and CI are missing
+    #[serde(default)]
+    pub skeleton: bool,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DashboardResponse {
     pub my_prs: Vec<DashboardPR>,
     pub review_requests: Vec<DashboardPR>,
+    /// Bot-authored review requests, populated when `bot_prs` is "group"
+    #[serde(default)]
+    pub bot_prs: Vec<DashboardPR>,
+    /// Login of the first account that fetched successfully
     pub github_username: String,
+    /// Per-account outcome, so one account's failure doesn't hide the others
+    #[serde(default)]
+    pub accounts: Vec<DashboardAccountStatus>,
+    #[serde(default)]
+    pub warnings: Vec<DashboardWarning>,
+    /// What changed since the previous fetch; empty on the first one
+    #[serde(default)]
+    pub changes: Vec<DashboardChange>,
     pub fetched_at: String,
+    /// Served from the on-disk cache rather than a live fetch
+    #[serde(default)]
+    pub stale: bool,
+    #[serde(default)]
+    pub refresh_stats: RefreshStats,
+    /// Number of the poller fetch that produced this, increasing; 0 outside
+    /// the poller
+    #[serde(default)]
+    pub generation: u64,
+}
+
+/// How much work a refresh took, summed over accounts.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshStats {
+    /// PRs whose reviews, reviewers, timeline and pull detail were fetched
+    /// again
+    pub enriched: usize,
+    /// PRs rebuilt from the previous refresh's details; their CI is fetched
+    /// unless it finished for the same head commit
+    pub reused: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardAccountStatus {
+    pub label: String,
+    pub login: Option<String>,
+    pub error: Option<String>,
+    /// Token expiry (ISO-8601), when the token has one
+    #[serde(default)]
+    pub token_expires_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DashboardWarningKind {
+    TokenExpiring,
+    MissingScopes,
+    SsoRequired,
+}
+
+/// A non-fatal problem worth showing above the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardWarning {
+    pub kind: DashboardWarningKind,
+    pub account: String,
+    pub message: String,
+    /// Where the user can fix it, e.g. the SAML SSO authorization page
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenType {
+    /// "ghp_" personal access token with OAuth scopes
+    Classic,
+    /// Permissions instead of scopes: a "github_pat_" fine-grained PAT or a
+    /// "ghu_" GitHub App user token. Neither sends X-OAuth-Scopes.
+    FineGrained,
+    /// "gho_" token from an OAuth App sign-in
+    Oauth,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub user: GitHubAuthenticatedUser,
+    pub token_type: TokenType,
+    /// Scopes granted to the token (X-OAuth-Scopes); empty for fine-grained
+    /// and GitHub App tokens, which don't use scopes
+    pub scopes: Vec<String>,
+    /// Scopes the /user endpoint accepts (X-Accepted-OAuth-Scopes)
+    pub accepted_scopes: Vec<String>,
+    /// Required scopes the token lacks; only checked for scoped tokens
+    pub missing_scopes: Vec<String>,
+    /// Expiry as ISO-8601 (GitHub-Authentication-Token-Expiration), if any
+    pub expires_at: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -180,7 +430,29 @@ fn parse_repo(repository_url: &str) -> String {
     }
 }
 
-fn build_headers(token: &str) -> HeaderMap {
+/// Connection details for one GitHub account: the shared HTTP client, the REST
+/// API root (https://api.github.com, or https://HOST/api/v3 on GHES) and the
+/// account's token.
+#[derive(Clone, Copy)]
+struct GitHubApi<'a> {
+    client: &'a reqwest::Client,
+    base_url: &'a str,
+    token: &'a str,
+}
+
+impl<'a> GitHubApi<'a> {
+    fn new(client: &'a reqwest::Client, base_url: &'a str, token: &'a str) -> Self {
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/'),
+            token,
+        }
+    }
+}
+
+/// Fails when the token has characters a header can't carry, e.g. a stray
+/// newline from a hand edit.
+fn build_headers(token: &str) -> Result<HeaderMap, String> {
     let mut headers = HeaderMap::new();
     headers.insert(
         ACCEPT,
@@ -189,27 +461,89 @@ fn build_headers(token: &str) -> HeaderMap {
     headers.insert(
         AUTHORIZATION,
         HeaderValue::from_str(&format!("Bearer {}", token))
-            .expect("invalid token characters"),
+            .map_err(|_| "The token contains invalid characters".to_string())?,
     );
     headers.insert(
         USER_AGENT,
         HeaderValue::from_static("gh-dash-desktop"),
     );
-    headers
+    Ok(headers)
 }
 
-/// Generic GitHub API fetch with rate-limit detection.
-async fn github_fetch<T: serde::de::DeserializeOwned>(
-    client: &reqwest::Client,
-    url: &str,
-    token: &str,
-) -> Result<T, String> {
-    let response = client
+/// Prefix of errors where GitHub couldn't be reached at all. Kept through the
+/// wrapping done by callers, so `is_network_error` can find it.
+const NETWORK_ERROR_PREFIX: &str = "Network error: ";
+
+/// Whether a fetch error means GitHub was unreachable (offline, DNS, TLS)
+/// rather than an API or token problem.
+pub fn is_network_error(message: &str) -> bool {
+    message.contains(NETWORK_ERROR_PREFIX)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RateLimit {
+    remaining: u64,
+    limit: u64,
+    /// UNIX time the window resets
+    reset: u64,
+}
+
+/// Latest `x-ratelimit-*` headers by API base URL and resource ("core",
+/// "graphql", ...), for adaptive polling.
+static RATE_LIMITS: Mutex<Option<HashMap<(String, String), RateLimit>>> = Mutex::new(None);
+
+fn record_rate_limit(base_url: &str, headers: &HeaderMap) {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let number = |name: &str| header(name).and_then(|v| v.parse::<u64>().ok());
+    let (Some(remaining), Some(limit), Some(reset)) = (
+        number("x-ratelimit-remaining"),
+        number("x-ratelimit-limit"),
+        number("x-ratelimit-reset"),
+    ) else {
+        return;
+    };
+    let resource = header("x-ratelimit-resource").unwrap_or("core").to_string();
+    RATE_LIMITS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(
+            (base_url.to_string(), resource),
+            RateLimit {
+                remaining,
+                limit,
+                reset,
+            },
+        );
+}
+
+/// Smallest fraction of a rate limit left, from 0 to 1, across the APIs the
+/// dashboard has used; `None` before any response. Windows that have reset
+/// are ignored, as is search, whose one-minute window refills between polls.
+pub fn rate_limit_headroom() -> Option<f64> {
+    let now = unix_now();
+    let limits = RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+    limits
+        .as_ref()?
+        .iter()
+        .filter(|((_, resource), limit)| {
+            resource != "search" && limit.reset > now && limit.limit > 0
+        })
+        .map(|(_, limit)| limit.remaining as f64 / limit.limit as f64)
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// Generic GitHub API GET with rate-limit detection. Returns the successful
+/// response so callers can inspect headers before reading the body.
+async fn github_get(api: GitHubApi<'_>, url: &str) -> Result<reqwest::Response, String> {
+    let response = api
+        .client
         .get(url)
-        .headers(build_headers(token))
+        .headers(build_headers(api.token)?)
         .send()
         .await
-        .map_err(|e| format!("Network error: {}", e))?;
+        .map_err(|e| format!("{}{}", NETWORK_ERROR_PREFIX, e))?;
+    record_rate_limit(api.base_url, response.headers());
 
     let status = response.status();
 
@@ -242,6 +576,21 @@ async fn github_fetch<T: serde::de::DeserializeOwned>(
         }
 
         let status_code = status.as_u16();
+        // A discovered token may have been revoked or replaced since
+        if status_code == 401 {
+            credentials::forget_discovered_tokens();
+        }
+        if status_code == 403 {
+            let sso = response
+                .headers()
+                .get("x-github-sso")
+                .and_then(|v| v.to_str().ok())
+                .and_then(SsoRequired::from_header);
+            if let Some(sso) = sso {
+                return Err(sso.to_error());
+            }
+        }
+
         let reason = status.canonical_reason().unwrap_or("Unknown");
         let body = response.text().await.unwrap_or_default();
         let body_preview = if body.len() > 200 { &body[..200] } else { &body };
@@ -251,117 +600,405 @@ async fn github_fetch<T: serde::de::DeserializeOwned>(
         ));
     }
 
-    response
+    Ok(response)
+}
+
+/// Generic GitHub API fetch with rate-limit detection.
+async fn github_fetch<T: serde::de::DeserializeOwned>(
+    api: GitHubApi<'_>,
+    url: &str,
+) -> Result<T, String> {
+    github_get(api, url)
+        .await?
         .json::<T>()
         .await
         .map_err(|e| format!("Failed to parse GitHub response: {}", e))
 }
 
-/// Format a UNIX timestamp into a local time string.
+/// Extract the `rel="last"` URL from a GitHub `Link` pagination header.
+fn parse_last_page_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, rel) = part.split_once(';')?;
+        if rel.trim() == "rel=\"last\"" {
+            Some(url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Format a UNIX timestamp as a local "HH:MM:SS" string.
 fn chrono_timestamp_to_local_time(ts: i64) -> String {
-    // Simple formatting without pulling in the chrono crate.
-    // We produce a UTC time string; close enough for a reset message.
-    let secs = ts;
-    let hours = (secs % 86400) / 3600;
-    let minutes = (secs % 3600) / 60;
-    let seconds = secs % 60;
-    // Approximate — give HH:MM:SS UTC
-    format!("{:02}:{:02}:{:02} UTC", hours, minutes, seconds)
+    DateTime::from_timestamp(ts, 0)
+        .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Token inspection
+// ---------------------------------------------------------------------------
+
+/// Scopes the dashboard needs, matching the web app's documented PAT scopes.
+const REQUIRED_SCOPES: &[&str] = &["repo", "read:org"];
+
+fn token_type(token: &str) -> TokenType {
+    if token.starts_with("github_pat_") || token.starts_with("ghu_") {
+        TokenType::FineGrained
+    } else if token.starts_with("ghp_") {
+        TokenType::Classic
+    } else if token.starts_with("gho_") {
+        TokenType::Oauth
+    } else {
+        TokenType::Unknown
+    }
+}
+
+/// "repo, read:org" -> ["repo", "read:org"]
+fn parse_scope_list(header: Option<&str>) -> Vec<String> {
+    header
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Required scopes not covered by the granted ones. Broader org scopes
+/// (write:org, admin:org) imply read:org.
+fn missing_required_scopes(granted: &[String]) -> Vec<String> {
+    REQUIRED_SCOPES
+        .iter()
+        .filter(|required| {
+            let implied_by: &[&str] = match **required {
+                "read:org" => &["read:org", "write:org", "admin:org"],
+                _ => std::slice::from_ref(*required),
+            };
+            !granted.iter().any(|g| implied_by.contains(&g.as_str()))
+        })
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// "2024-06-05 18:25:31 UTC" or "2024-06-05 18:25:31 -0700" -> ISO-8601 UTC.
+fn parse_token_expiration(header: &str) -> Option<String> {
+    let utc = match header.strip_suffix(" UTC") {
+        Some(naive) => NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S")
+            .ok()?
+            .and_utc(),
+        None => DateTime::parse_from_str(header, "%Y-%m-%d %H:%M:%S %z")
+            .ok()?
+            .with_timezone(&Utc),
+    };
+    Some(format_iso(utc))
+}
+
+/// Warnings for a token that expires within `warning_days` or lacks scopes
+/// the dashboard relies on.
+fn token_warnings(
+    account: &str,
+    token: &TokenInfo,
+    warning_days: u64,
+    now: u64,
+) -> Vec<DashboardWarning> {
+    let mut warnings = Vec::new();
+
+    let expires = token.expires_at.as_deref().and_then(parse_iso_timestamp);
+    if let Some(expires) = expires {
+        if expires <= now + warning_days * 86400 {
+            let message = if expires <= now {
+                "GitHub token has expired. Generate a new one.".to_string()
+            } else {
+                format!(
+                    "GitHub token expires in {}. Generate a new one before then.",
+                    humanize_duration(expires - now)
+                )
+            };
+            warnings.push(DashboardWarning {
+                kind: DashboardWarningKind::TokenExpiring,
+                account: account.to_string(),
+                message,
+                url: None,
+            });
+        }
+    }
+
+    if !token.missing_scopes.is_empty() {
+        warnings.push(DashboardWarning {
+            kind: DashboardWarningKind::MissingScopes,
+            account: account.to_string(),
+            message: format!(
+                "GitHub token is missing scopes: {}. Some PRs may not appear.",
+                token.missing_scopes.join(", ")
+            ),
+            url: None,
+        });
+    }
+
+    warnings
+}
+
+// ---------------------------------------------------------------------------
+// SAML SSO
+// ---------------------------------------------------------------------------
+
+const SSO_REQUIRED_PREFIX: &str = "SSO_REQUIRED: ";
+
+/// A 403 caused by an organization's SAML SSO enforcement. Carried through
+/// the `Result<_, String>` plumbing as "SSO_REQUIRED: " followed by JSON, so
+/// the frontend can show the authorization link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoRequired {
+    pub organization: Option<String>,
+    pub url: String,
+}
+
+impl SsoRequired {
+    /// "required; url=https://github.com/orgs/acme/sso?authorization_request=..."
+    fn from_header(header: &str) -> Option<Self> {
+        let mut parts = header.split(';').map(str::trim);
+        if parts.next()? != "required" {
+            return None;
+        }
+        let url = parts.find_map(|part| part.strip_prefix("url="))?.to_string();
+        let organization = url
+            .split_once("/orgs/")
+            .and_then(|(_, rest)| rest.split('/').next())
+            .filter(|org| !org.is_empty())
+            .map(str::to_string);
+        Some(Self { organization, url })
+    }
+
+    fn to_error(&self) -> String {
+        format!(
+            "{}{}",
+            SSO_REQUIRED_PREFIX,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    fn from_error(msg: &str) -> Option<Self> {
+        serde_json::from_str(msg.strip_prefix(SSO_REQUIRED_PREFIX)?).ok()
+    }
+
+    fn message(&self) -> String {
+        match self.organization {
+            Some(ref org) => format!(
+                "PRs from {} are hidden: authorize your token for its SAML SSO.",
+                org
+            ),
+            None => "Some PRs are hidden: authorize your token for SAML SSO.".to_string(),
+        }
+    }
+}
+
+/// Organization IDs from a search response's
+/// "X-GitHub-SSO: partial-results; organizations=21955855,20582480" header,
+/// whose results GitHub left out because the token isn't SSO-authorized.
+fn parse_sso_partial_results(headers: &HeaderMap) -> Vec<String> {
+    let Some(header) = headers.get("x-github-sso").and_then(|v| v.to_str().ok()) else {
+        return Vec::new();
+    };
+    let mut parts = header.split(';').map(str::trim);
+    if parts.next() != Some("partial-results") {
+        return Vec::new();
+    }
+    parts
+        .find_map(|part| part.strip_prefix("organizations="))
+        .unwrap_or_default()
+        .split(',')
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
 // GitHub API fetchers
 // ---------------------------------------------------------------------------
 
-async fn fetch_authenticated_user(
-    client: &reqwest::Client,
-    token: &str,
-) -> Result<GitHubAuthenticatedUser, String> {
-    github_fetch(client, &format!("{}/user", GITHUB_API), token).await
+/// Resolve the authenticated user and read the token's scopes and expiry from
+/// the response headers.
+async fn fetch_token_info(api: GitHubApi<'_>) -> Result<TokenInfo, String> {
+    let response = github_get(api, &format!("{}/user", api.base_url)).await?;
+    let headers = response.headers().clone();
+    let user: GitHubAuthenticatedUser = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub response: {}", e))?;
+
+    let header_str = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let token_type = token_type(api.token);
+    let scopes = parse_scope_list(header_str("x-oauth-scopes"));
+    let missing_scopes = match token_type {
+        TokenType::FineGrained => Vec::new(),
+        _ => missing_required_scopes(&scopes),
+    };
+
+    Ok(TokenInfo {
+        user,
+        token_type,
+        scopes,
+        accepted_scopes: parse_scope_list(header_str("x-accepted-oauth-scopes")),
+        missing_scopes,
+        expires_at: header_str("github-authentication-token-expiration")
+            .and_then(parse_token_expiration),
+    })
 }
 
-async fn fetch_my_prs(
-    client: &reqwest::Client,
-    username: &str,
-    token: &str,
-) -> Result<Vec<GitHubSearchItem>, String> {
+/// Open PRs matching a search query, plus the IDs of organizations whose
+/// results were withheld for SAML SSO.
+struct SearchResults {
+    items: Vec<GitHubSearchItem>,
+    sso_hidden_orgs: Vec<String>,
+}
+
+async fn search_prs(api: GitHubApi<'_>, query: &str) -> Result<SearchResults, String> {
+    let q = urlencoding::encode(query);
+    let url = format!("{}/search/issues?q={}&per_page=25", api.base_url, q);
+    let response = github_get(api, &url).await?;
+    let sso_hidden_orgs = parse_sso_partial_results(response.headers());
+    let data: GitHubSearchResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub response: {}", e))?;
+    Ok(SearchResults {
+        items: data
+            .items
+            .into_iter()
+            .filter(|item| item.pull_request.is_some())
+            .collect(),
+        sso_hidden_orgs,
+    })
+}
+
+async fn fetch_my_prs(api: GitHubApi<'_>, username: &str) -> Result<SearchResults, String> {
     let query = format!("author:{} type:pr state:open sort:updated", username);
-    let q = urlencoding::encode(&query);
-    let url = format!("{}/search/issues?q={}&per_page=25", GITHUB_API, q);
-    let data: GitHubSearchResponse = github_fetch(client, &url, token).await?;
-    Ok(data
-        .items
-        .into_iter()
-        .filter(|item| item.pull_request.is_some())
-        .collect())
+    search_prs(api, &query).await
 }
 
 async fn fetch_review_requests(
-    client: &reqwest::Client,
+    api: GitHubApi<'_>,
     username: &str,
-    token: &str,
-) -> Result<Vec<GitHubSearchItem>, String> {
+) -> Result<SearchResults, String> {
     let query = format!("review-requested:{} type:pr state:open sort:updated", username);
-    let q = urlencoding::encode(&query);
-    let url = format!("{}/search/issues?q={}&per_page=25", GITHUB_API, q);
-    let data: GitHubSearchResponse = github_fetch(client, &url, token).await?;
-    Ok(data
-        .items
-        .into_iter()
-        .filter(|item| item.pull_request.is_some())
-        .collect())
+    search_prs(api, &query).await
 }
 
 async fn fetch_reviewed_by(
-    client: &reqwest::Client,
+    api: GitHubApi<'_>,
     username: &str,
-    token: &str,
-) -> Result<Vec<GitHubSearchItem>, String> {
+) -> Result<SearchResults, String> {
     let query = format!("reviewed-by:{} type:pr state:open sort:updated", username);
-    let q = urlencoding::encode(&query);
-    let url = format!("{}/search/issues?q={}&per_page=25", GITHUB_API, q);
-    let data: GitHubSearchResponse = github_fetch(client, &url, token).await?;
-    Ok(data
-        .items
-        .into_iter()
-        .filter(|item| item.pull_request.is_some())
-        .collect())
+    search_prs(api, &query).await
 }
 
 async fn fetch_reviews(
-    client: &reqwest::Client,
+    api: GitHubApi<'_>,
     owner: &str,
     repo: &str,
     pr_number: u64,
-    token: &str,
 ) -> Result<Vec<GitHubReview>, String> {
     let url = format!(
         "{}/repos/{}/{}/pulls/{}/reviews",
-        GITHUB_API, owner, repo, pr_number
+        api.base_url, owner, repo, pr_number
     );
-    github_fetch(client, &url, token).await
+    github_fetch(api, &url).await
 }
 
 async fn fetch_requested_reviewers(
-    client: &reqwest::Client,
+    api: GitHubApi<'_>,
     owner: &str,
     repo: &str,
     pr_number: u64,
-    token: &str,
 ) -> Result<GitHubRequestedReviewersResponse, String> {
     let url = format!(
         "{}/repos/{}/{}/pulls/{}/requested_reviewers",
-        GITHUB_API, owner, repo, pr_number
+        api.base_url, owner, repo, pr_number
     );
-    github_fetch(client, &url, token).await
+    github_fetch(api, &url).await
 }
 
 async fn fetch_pull_detail(
-    client: &reqwest::Client,
+    api: GitHubApi<'_>,
     pull_url: &str,
-    token: &str,
 ) -> Result<GitHubPullDetail, String> {
-    github_fetch(client, pull_url, token).await
+    github_fetch(api, pull_url).await
+}
+
+/// CI result for a commit from both the Checks and Statuses APIs. Failures
+/// to read either (e.g. a token without checks access) leave it unknown
+/// rather than failing the PR.
+async fn fetch_ci_status(
+    api: GitHubApi<'_>,
+    owner: &str,
+    repo: &str,
+    sha: &str,
+) -> Option<CiStatus> {
+    let check_runs_url = format!(
+        "{}/repos/{}/{}/commits/{}/check-runs?per_page=100",
+        api.base_url, owner, repo, sha
+    );
+    let status_url = format!(
+        "{}/repos/{}/{}/commits/{}/status",
+        api.base_url, owner, repo, sha
+    );
+    let (check_runs, combined) = tokio::join!(
+        github_fetch::<GitHubCheckRunsResponse>(api, &check_runs_url),
+        github_fetch::<GitHubCombinedStatus>(api, &status_url)
+    );
+    let check_runs = check_runs.map(|r| r.check_runs).unwrap_or_default();
+    combine_ci_status(&check_runs, combined.ok().as_ref())
+}
+
+fn combine_ci_status(
+    check_runs: &[GitHubCheckRun],
+    combined: Option<&GitHubCombinedStatus>,
+) -> Option<CiStatus> {
+    let mut statuses: Vec<CiStatus> = check_runs
+        .iter()
+        .map(|run| match (run.status.as_str(), run.conclusion.as_deref()) {
+            ("completed", Some("success" | "neutral" | "skipped")) => CiStatus::Success,
+            ("completed", _) => CiStatus::Failure,
+            _ => CiStatus::Pending,
+        })
+        .collect();
+    // A commit with no statuses still reports "pending"
+    if let Some(combined) = combined.filter(|c| c.total_count > 0) {
+        statuses.push(match combined.state.as_str() {
+            "success" => CiStatus::Success,
+            "pending" => CiStatus::Pending,
+            _ => CiStatus::Failure,
+        });
+    }
+
+    [CiStatus::Failure, CiStatus::Pending, CiStatus::Success]
+        .into_iter()
+        .find(|status| statuses.contains(status))
+}
+
+/// Fetch the tail of the issue timeline. Events are returned oldest-first, so
+/// when there is more than one page only the last page is kept — the current
+/// turn almost always starts within the last 100 events.
+async fn fetch_timeline(
+    api: GitHubApi<'_>,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+) -> Result<Vec<GitHubTimelineEvent>, String> {
+    let url = format!(
+        "{}/repos/{}/{}/issues/{}/timeline?per_page=100",
+        api.base_url, owner, repo, pr_number
+    );
+    let response = github_get(api, &url).await?;
+    let response = match parse_last_page_link(response.headers()) {
+        Some(last_url) => github_get(api, &last_url).await?,
+        None => response,
+    };
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub response: {}", e))
 }
 
 // ---------------------------------------------------------------------------
@@ -375,6 +1012,34 @@ fn is_submitted_state(state: &str) -> bool {
     )
 }
 
+/// Latest effective review per reviewer, in order of each reviewer's first
+/// review. COMMENTED does not clear an earlier CHANGES_REQUESTED or APPROVED.
+fn latest_reviews_by_user(reviews: &[GitHubReview]) -> Vec<&GitHubReview> {
+    let mut order: Vec<String> = Vec::new();
+    let mut latest_by_user: HashMap<String, &GitHubReview> = HashMap::new();
+    for review in reviews {
+        if !is_submitted_state(&review.state) {
+            continue;
+        }
+        let login = review.user.login.to_lowercase();
+        match latest_by_user.get(&login) {
+            Some(prev) => {
+                if (prev.state == "CHANGES_REQUESTED" || prev.state == "APPROVED")
+                    && review.state == "COMMENTED"
+                {
+                    continue;
+                }
+            }
+            None => order.push(login.clone()),
+        }
+        latest_by_user.insert(login, review);
+    }
+    order
+        .iter()
+        .filter_map(|login| latest_by_user.get(login).copied())
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Turn determination — My PRs
 // ---------------------------------------------------------------------------
@@ -389,12 +1054,21 @@ fn determine_my_pr_turn(
     requested_reviewers: &[GitHubUser],
     author_username: &str,
     mergeable_state: Option<&str>,
+    turn_rules: &TurnRules,
 ) -> TurnResult {
     let mut checks: Vec<TurnDebugCheck> = Vec::new();
     let deciding_check: String;
     let author_lower = author_username.to_lowercase();
 
-    // Step 1: Identify reviewers who have submitted feedback (excluding author)
+    // Bot reviews (Copilot, linters) never put the ball in the author's court
+    let human_reviews: Vec<GitHubReview> = reviews
+        .iter()
+        .filter(|review| !review.user.is_bot())
+        .cloned()
+        .collect();
+    let reviews = human_reviews.as_slice();
+
+    // Step 1: Identify reviewers who have submitted feedback (excluding author and bots)
     let mut reviewers_who_submitted: HashSet<String> = HashSet::new();
     for review in reviews {
         if is_submitted_state(&review.state)
@@ -481,22 +1155,11 @@ fn determine_my_pr_turn(
 
     // Step 4: Compute latest review state per user
     // COMMENTED does not clear CHANGES_REQUESTED or APPROVED
-    let mut latest_by_user: HashMap<String, String> = HashMap::new();
-    for review in reviews {
-        if is_submitted_state(&review.state)
-            && review.user.login.to_lowercase() != author_lower
-        {
-            let login = review.user.login.to_lowercase();
-            if let Some(prev) = latest_by_user.get(&login) {
-                if (prev == "CHANGES_REQUESTED" || prev == "APPROVED")
-                    && review.state == "COMMENTED"
-                {
-                    continue;
-                }
-            }
-            latest_by_user.insert(login, review.state.clone());
-        }
-    }
+    let latest_by_user: HashMap<String, String> = latest_reviews_by_user(reviews)
+        .into_iter()
+        .filter(|review| review.user.login.to_lowercase() != author_lower)
+        .map(|review| (review.user.login.to_lowercase(), review.state.clone()))
+        .collect();
 
     // Step 5: If any reviewer's latest state is CHANGES_REQUESTED, always my-turn
     let has_changes_requested = latest_by_user.values().any(|s| s == "CHANGES_REQUESTED");
@@ -540,7 +1203,7 @@ fn determine_my_pr_turn(
 
     // Step 6: No changes requested — check mergeable_state
     let state_str = mergeable_state.unwrap_or("null");
-    let (merge_result, merge_desc) = match state_str {
+    let (mut merge_result, mut merge_desc) = match state_str {
         "clean" => (
             TurnStatus::MyTurn,
             "Ready to merge — all branch protection met".to_string(),
@@ -562,6 +1225,10 @@ fn determine_my_pr_turn(
             "Unknown/null — conservative fallback".to_string(),
         ),
     };
+    if let Some((key, status)) = turn_rules.for_state(state_str) {
+        merge_result = status;
+        merge_desc = format!("{} (overridden by turn_rules.{})", merge_desc, key);
+    }
 
     checks.push(TurnDebugCheck {
         label: format!("Mergeable state: {}", state_str),
@@ -679,53 +1346,104 @@ fn determine_review_request_turn(
 }
 
 // ---------------------------------------------------------------------------
-// Review summary
+// Reviewers
 // ---------------------------------------------------------------------------
 
-fn build_review_summary(
+fn reviewer_state(state: &str) -> ReviewerState {
+    match state {
+        "APPROVED" => ReviewerState::Approved,
+        "CHANGES_REQUESTED" => ReviewerState::ChangesRequested,
+        "DISMISSED" => ReviewerState::Dismissed,
+        _ => ReviewerState::Commented,
+    }
+}
+
+/// Per-reviewer state: everyone who has reviewed (excluding the author), then
+/// requested users who haven't reviewed yet, then requested teams.
+fn build_reviewers(
     reviews: &[GitHubReview],
     requested_reviewers: &[GitHubUser],
     requested_teams: &[GitHubTeam],
-) -> String {
-    let mut parts: Vec<String> = Vec::new();
+    author_username: &str,
+) -> Vec<DashboardReviewer> {
+    let author_lower = author_username.to_lowercase();
+    let requested_logins: HashSet<String> = requested_reviewers
+        .iter()
+        .map(|r| r.login.to_lowercase())
+        .collect();
 
-    // Count latest review state per reviewer.
-    // COMMENTED does not override CHANGES_REQUESTED or APPROVED.
-    let mut latest_by_user: HashMap<String, String> = HashMap::new();
-    for review in reviews {
-        if is_submitted_state(&review.state) {
-            let login = &review.user.login;
-            if let Some(prev) = latest_by_user.get(login) {
-                if (prev == "CHANGES_REQUESTED" || prev == "APPROVED")
-                    && review.state == "COMMENTED"
-                {
-                    continue;
-                }
-            }
-            latest_by_user.insert(login.clone(), review.state.clone());
+    let mut reviewers: Vec<DashboardReviewer> = latest_reviews_by_user(reviews)
+        .into_iter()
+        .filter(|review| review.user.login.to_lowercase() != author_lower)
+        .map(|review| DashboardReviewer {
+            login: review.user.login.clone(),
+            avatar_url: Some(review.user.avatar_url.clone()),
+            team_slug: None,
+            state: reviewer_state(&review.state),
+            submitted_at: review.submitted_at.clone(),
+            requested_again: requested_logins.contains(&review.user.login.to_lowercase()),
+        })
+        .collect();
+
+    let reviewed_logins: HashSet<String> =
+        reviewers.iter().map(|r| r.login.to_lowercase()).collect();
+    for user in requested_reviewers {
+        if !reviewed_logins.contains(&user.login.to_lowercase()) {
+            reviewers.push(DashboardReviewer {
+                login: user.login.clone(),
+                avatar_url: Some(user.avatar_url.clone()),
+                team_slug: None,
+                state: ReviewerState::Pending,
+                submitted_at: None,
+                requested_again: false,
+            });
         }
     }
 
-    let mut counts: HashMap<String, u32> = HashMap::new();
-    for state in latest_by_user.values() {
-        *counts.entry(state.clone()).or_insert(0) += 1;
+    for team in requested_teams {
+        reviewers.push(DashboardReviewer {
+            login: team.name.clone(),
+            avatar_url: None,
+            team_slug: Some(team.slug.clone()),
+            state: ReviewerState::Pending,
+            submitted_at: None,
+            requested_again: false,
+        });
     }
 
-    if let Some(&count) = counts.get("APPROVED") {
-        parts.push(format!("{} approved", count));
+    reviewers
+}
+
+/// "2 approved, 1 changes requested, 1 pending (1 team)"
+fn build_review_summary(reviewers: &[DashboardReviewer]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    let count_state =
+        |state: ReviewerState| reviewers.iter().filter(|r| r.state == state).count();
+    let approved = count_state(ReviewerState::Approved);
+    let changes_requested = count_state(ReviewerState::ChangesRequested);
+    let commented = count_state(ReviewerState::Commented);
+
+    if approved > 0 {
+        parts.push(format!("{} approved", approved));
     }
-    if let Some(&count) = counts.get("CHANGES_REQUESTED") {
-        parts.push(format!("{} changes requested", count));
+    if changes_requested > 0 {
+        parts.push(format!("{} changes requested", changes_requested));
     }
-    if let Some(&count) = counts.get("COMMENTED") {
-        parts.push(format!("{} commented", count));
+    if commented > 0 {
+        parts.push(format!("{} commented", commented));
     }
 
-    let pending_count = requested_reviewers.len() + requested_teams.len();
+    // Re-requested reviewers count as pending alongside those not yet reviewed
+    let team_count = reviewers.iter().filter(|r| r.team_slug.is_some()).count();
+    let pending_count = reviewers
+        .iter()
+        .filter(|r| r.state == ReviewerState::Pending || r.requested_again)
+        .count();
     if pending_count > 0 {
-        let team_suffix = if !requested_teams.is_empty() {
-            let plural = if requested_teams.len() > 1 { "s" } else { "" };
-            format!(" ({} team{})", requested_teams.len(), plural)
+        let team_suffix = if team_count > 0 {
+            let plural = if team_count > 1 { "s" } else { "" };
+            format!(" ({} team{})", team_count, plural)
         } else {
             String::new()
         };
@@ -739,18 +1457,247 @@ fn build_review_summary(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Turn timing
+// ---------------------------------------------------------------------------
+
+/// Which side of the PR a timeline event hands the ball to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TurnSide {
+    Author,
+    Reviewers,
+}
+
+/// Classify a timeline event for the given section. Returns the event time
+/// and the side it hands the turn to, or `None` for events that don't move
+/// the turn.
+fn classify_timeline_event<'a>(
+    event: &'a GitHubTimelineEvent,
+    section: &str,
+    author_username: &str,
+    my_username: &str,
+) -> Option<(&'a str, TurnSide)> {
+    let author_lower = author_username.to_lowercase();
+    let my_lower = my_username.to_lowercase();
+
+    match event.event.as_deref()? {
+        "reviewed" => {
+            let user = event.user.as_ref()?;
+            if user.is_bot() {
+                return None;
+            }
+            let login = user.login.to_lowercase();
+            let side = if login == author_lower {
+                // Author replying to review threads hands the ball back
+                TurnSide::Reviewers
+            } else if section == "my-prs" || login == my_lower {
+                TurnSide::Author
+            } else {
+                // Another reviewer's review doesn't change whether I owe one
+                return None;
+            };
+            Some((event.submitted_at.as_deref()?, side))
+        }
+        "review_requested" => {
+            if section == "review-requests" {
+                let requested_me = event
+                    .requested_reviewer
+                    .as_ref()
+                    .is_some_and(|u| u.login.to_lowercase() == my_lower);
+                if !requested_me && event.requested_team.is_none() {
+                    return None;
+                }
+            }
+            Some((event.created_at.as_deref()?, TurnSide::Reviewers))
+        }
+        "committed" => Some((
+            event.committer.as_ref()?.date.as_deref()?,
+            TurnSide::Reviewers,
+        )),
+        "ready_for_review" => Some((event.created_at.as_deref()?, TurnSide::Reviewers)),
+        _ => None,
+    }
+}
+
+/// When the current turn began: the earliest event in the trailing run of
+/// events that handed the ball to whoever holds it now. If the latest event
+/// points the other way (e.g. my turn because of a merge conflict), the turn
+/// began no earlier than that event. PRs without relevant events fall back to
+/// `created_at`.
+fn determine_turn_since(
+    timeline: &[GitHubTimelineEvent],
+    section: &str,
+    turn_status: &TurnStatus,
+    author_username: &str,
+    my_username: &str,
+    created_at: &str,
+) -> String {
+    let holder = match (section, turn_status) {
+        ("my-prs", TurnStatus::MyTurn) => TurnSide::Author,
+        ("my-prs", TurnStatus::TheirTurn) => TurnSide::Reviewers,
+        (_, TurnStatus::MyTurn) => TurnSide::Reviewers,
+        (_, TurnStatus::TheirTurn) => TurnSide::Author,
+    };
+
+    let mut events: Vec<(&str, TurnSide)> = timeline
+        .iter()
+        .filter_map(|e| classify_timeline_event(e, section, author_username, my_username))
+        .collect();
+    // Commit dates can predate the push, so order by timestamp explicitly
+    events.sort_by(|a, b| a.0.cmp(b.0));
+
+    let Some(&(latest_ts, _)) = events.last() else {
+        return created_at.to_string();
+    };
+
+    events
+        .iter()
+        .rev()
+        .take_while(|(_, side)| *side == holder)
+        .last()
+        .map(|(ts, _)| ts.to_string())
+        .unwrap_or_else(|| latest_ts.to_string())
+}
+
 // ---------------------------------------------------------------------------
 // PR enrichment
 // ---------------------------------------------------------------------------
 
+/// Reviews, requested reviewers and timeline of a PR. These only change
+/// when the PR's `updated_at` moves, so they are cached across refreshes.
+struct ReviewDetails {
+    reviews: Vec<GitHubReview>,
+    requested: GitHubRequestedReviewersResponse,
+    timeline: Vec<GitHubTimelineEvent>,
+}
+
+/// Everything fetched from GitHub for a single PR during enrichment.
+struct PrDetails {
+    review: Arc<ReviewDetails>,
+    pull_detail: Option<GitHubPullDetail>,
+    ci_status: Option<CiStatus>,
+}
+
+/// How long details are reused while a PR's `updated_at` stays put, and a
+/// finished CI result while its head commit does, as a backstop for changes
+/// that don't move either (a new base commit, a re-run check).
+const ENRICHMENT_TTL: Duration = Duration::from_secs(5 * 60);
+
+struct CachedDetails {
+    /// `updated_at` of the search item the details were fetched for
+    updated_at: String,
+    /// Whether the PR came from the review-requested search, which a
+    /// single-PR refresh can't rerun
+    is_review_requested: bool,
+    fetched: Instant,
+    review: Arc<ReviewDetails>,
+    pull_detail: Option<GitHubPullDetail>,
+    ci: Option<CachedCi>,
+}
+
+/// CI status of one head commit.
+#[derive(Clone)]
+struct CachedCi {
+    head_sha: String,
+    status: Option<CiStatus>,
+    fetched: Instant,
+}
+
+impl CachedCi {
+    /// Whether the status can stand for `head_sha`: same commit, checks
+    /// finished, and not older than `ENRICHMENT_TTL`. Pending checks (or none
+    /// reported yet) are asked for again on every refresh.
+    fn reusable_for(&self, head_sha: &str) -> bool {
+        self.head_sha == head_sha
+            && matches!(self.status, Some(CiStatus::Success | CiStatus::Failure))
+            && self.fetched.elapsed() < ENRICHMENT_TTL
+    }
+}
+
+/// Details by account label and PR id, so a refresh only asks GitHub about
+/// PRs that changed: reviews, timeline and pull detail when `updated_at`
+/// moved, CI when the head commit moved or its checks are still running.
+static ENRICHMENT_CACHE: Mutex<Option<HashMap<(String, u64), CachedDetails>>> = Mutex::new(None);
+
+/// Drop cached details of an account's PRs that are no longer on its
+/// dashboard.
+fn prune_enrichment_cache(account: &str, current_ids: &HashSet<u64>) {
+    let mut cache = ENRICHMENT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(cache) = cache.as_mut() {
+        cache.retain(|(label, id), _| label != account || current_ids.contains(id));
+    }
+}
+
+/// Drop the entry of a PR that left the dashboard.
+fn forget_cached_details(account: &str, id: u64) {
+    if let Some(cache) = ENRICHMENT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+    {
+        cache.remove(&(account.to_string(), id));
+    }
+}
+
+/// Whether the cached PR came from the review-requested search, or `None`
+/// when it isn't cached.
+fn cached_review_requested(account: &str, id: u64) -> Option<bool> {
+    ENRICHMENT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(&(account.to_string(), id)))
+        .map(|cached| cached.is_review_requested)
+}
+
+/// Review details and pull detail cached for a search item, unless it was
+/// updated since or they are older than `ENRICHMENT_TTL`. A pull detail
+/// fetched before GitHub had worked out mergeability isn't reused either.
+fn cached_details(
+    key: &(String, u64),
+    item: &GitHubSearchItem,
+) -> Option<(Arc<ReviewDetails>, Option<GitHubPullDetail>)> {
+    let cache = ENRICHMENT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .as_ref()?
+        .get(key)
+        .filter(|cached| {
+            cached.updated_at == item.updated_at
+                && cached.fetched.elapsed() < ENRICHMENT_TTL
+                && cached.pull_detail.as_ref().is_none_or(|d| d.mergeable.is_some())
+        })
+        .map(|cached| (Arc::clone(&cached.review), cached.pull_detail.clone()))
+}
+
+/// The cached CI status of a PR, if it can stand for `head_sha`.
+fn cached_ci(key: &(String, u64), head_sha: &str) -> Option<CachedCi> {
+    let cache = ENRICHMENT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .as_ref()?
+        .get(key)?
+        .ci
+        .clone()
+        .filter(|ci| ci.reusable_for(head_sha))
+}
+
+/// Enrich a search item into a dashboard PR. Reviews, timeline and pull
+/// detail are reused from the last refresh unless `refetch` is set, the PR
+/// was updated since or they are older than `ENRICHMENT_TTL`; the flag says
+/// whether they were fetched again, in which case the cache entry is
+/// replaced. CI is reused while the head commit stays put and its checks are
+/// finished, also when the rest was fetched again.
+#[allow(clippy::too_many_arguments)]
 async fn enrich_pr(
-    client: &reqwest::Client,
+    api: GitHubApi<'_>,
+    account: &str,
     item: &GitHubSearchItem,
-    token: &str,
     section: &str,
     my_username: &str,
     is_review_requested: bool,
-) -> Result<DashboardPR, String> {
+    refetch: bool,
+    size_thresholds: &SizeThresholds,
+    rules: &Rules,
+) -> Result<(DashboardPR, bool), String> {
     let repo = parse_repo(&item.repository_url);
     let parts: Vec<&str> = repo.splitn(2, '/').collect();
     if parts.len() != 2 {
@@ -758,66 +1705,119 @@ async fn enrich_pr(
     }
     let owner = parts[0];
     let repo_name = parts[1];
-
-    // Parallel fetches: reviews, requested reviewers, and (for my-prs) pull detail
-    let reviews_fut = fetch_reviews(client, owner, repo_name, item.number, token);
-    let requested_reviewers_fut =
-        fetch_requested_reviewers(client, owner, repo_name, item.number, token);
-
-    let pull_detail = if section == "my-prs" {
-        if let Some(ref pr) = item.pull_request {
-            let detail_fut = fetch_pull_detail(client, &pr.url, token);
-            let (reviews_res, rr_res, detail_res) =
-                tokio::join!(reviews_fut, requested_reviewers_fut, detail_fut);
-            let reviews = reviews_res?;
-            let rr_data = rr_res?;
-            let detail = detail_res?;
-            return finish_enrich(
-                item,
-                &repo,
-                section,
-                my_username,
-                is_review_requested,
-                reviews,
-                rr_data,
-                Some(detail),
-            );
-        }
+    let turn_rules = &rules.for_repo(&repo).turn_rules;
+    let key = (account.to_string(), item.id);
+    let cached = if refetch {
         None
     } else {
-        None
+        cached_details(&key, item)
+    };
+    let refetched = cached.is_none();
+    let (cached_review, cached_pull_detail) = cached.unzip();
+
+    // Parallel fetches: reviews, requested reviewers and timeline, and pull
+    // detail followed by CI, each unless cached
+    let review_fut = async {
+        if let Some(review) = cached_review {
+            return Ok(review);
+        }
+        let (reviews, requested, timeline) = tokio::join!(
+            fetch_reviews(api, owner, repo_name, item.number),
+            fetch_requested_reviewers(api, owner, repo_name, item.number),
+            fetch_timeline(api, owner, repo_name, item.number)
+        );
+        Ok::<_, String>(Arc::new(ReviewDetails {
+            reviews: reviews?,
+            requested: requested?,
+            timeline: timeline?,
+        }))
+    };
+    // CI status needs the head SHA from the pull detail
+    let detail_fut = async {
+        let detail = match (cached_pull_detail, &item.pull_request) {
+            (Some(detail), _) => detail,
+            (None, Some(pr)) => Some(fetch_pull_detail(api, &pr.url).await?),
+            (None, None) => None,
+        };
+        let ci = match detail.as_ref().and_then(|d| d.head.as_ref()) {
+            Some(head) => match cached_ci(&key, &head.sha).filter(|_| !refetch) {
+                Some(ci) => Some(ci),
+                None => Some(CachedCi {
+                    head_sha: head.sha.clone(),
+                    status: fetch_ci_status(api, owner, repo_name, &head.sha).await,
+                    fetched: Instant::now(),
+                }),
+            },
+            None => None,
+        };
+        Ok::<_, String>((detail, ci))
     };
 
-    // Non my-prs path, or no pull_request URL
-    let (reviews_res, rr_res) = tokio::join!(reviews_fut, requested_reviewers_fut);
-    let reviews = reviews_res?;
-    let rr_data = rr_res?;
+    let (review_res, detail_res) = tokio::join!(review_fut, detail_fut);
+    let (pull_detail, ci) = detail_res?;
+    let details = PrDetails {
+        review: review_res?,
+        pull_detail,
+        ci_status: ci.as_ref().and_then(|ci| ci.status),
+    };
 
-    finish_enrich(
+    // Turn status and waiting time are recomputed even from cached details,
+    // since they depend on the rules and the clock
+    let pr = finish_enrich(
         item,
         &repo,
+        account,
         section,
         my_username,
         is_review_requested,
-        reviews,
-        rr_data,
-        pull_detail,
-    )
+        &details,
+        size_thresholds,
+        turn_rules,
+    )?;
+    let mut cache = ENRICHMENT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    if refetched {
+        cache.insert(
+            key,
+            CachedDetails {
+                updated_at: item.updated_at.clone(),
+                is_review_requested,
+                fetched: Instant::now(),
+                review: details.review,
+                pull_detail: details.pull_detail,
+                ci,
+            },
+        );
+    } else if let Some(cached) = cache.get_mut(&key) {
+        cached.ci = ci;
+    }
+    Ok((pr, refetched))
 }
 
 #[allow(clippy::too_many_arguments)]
 fn finish_enrich(
     item: &GitHubSearchItem,
     repo: &str,
+    account: &str,
     section: &str,
     my_username: &str,
     is_review_requested: bool,
-    reviews: Vec<GitHubReview>,
-    rr_data: GitHubRequestedReviewersResponse,
-    pull_detail: Option<GitHubPullDetail>,
+    details: &PrDetails,
+    size_thresholds: &SizeThresholds,
+    turn_rules: &TurnRules,
 ) -> Result<DashboardPR, String> {
-    let requested_reviewers = &rr_data.users;
-    let requested_teams = &rr_data.teams;
+    let PrDetails {
+        review,
+        pull_detail,
+        ci_status,
+    } = details;
+    let ReviewDetails {
+        reviews,
+        requested,
+        timeline,
+    } = &**review;
+    let requested_reviewers = &requested.users;
+    let requested_teams = &requested.teams;
     let mergeable_state = pull_detail.as_ref().and_then(|d| d.mergeable_state.as_deref());
 
     let TurnResult {
@@ -825,14 +1825,15 @@ fn finish_enrich(
         debug_info,
     } = if section == "my-prs" {
         determine_my_pr_turn(
-            &reviews,
+            reviews,
             requested_reviewers,
             &item.user.login,
             mergeable_state,
+            turn_rules,
         )
     } else {
         determine_review_request_turn(
-            &reviews,
+            reviews,
             requested_reviewers,
             requested_teams,
             my_username,
@@ -840,7 +1841,31 @@ fn finish_enrich(
         )
     };
 
-    let review_summary = build_review_summary(&reviews, requested_reviewers, requested_teams);
+    let turn_since = determine_turn_since(
+        timeline,
+        section,
+        &turn_status,
+        &item.user.login,
+        my_username,
+        &item.created_at,
+    );
+    let waiting_for = parse_iso_timestamp(&turn_since)
+        .map(|since| humanize_duration(unix_now().saturating_sub(since)));
+
+    let reviewers = build_reviewers(
+        reviews,
+        requested_reviewers,
+        requested_teams,
+        &item.user.login,
+    );
+    let review_summary = build_review_summary(&reviewers);
+    let size = pull_detail.as_ref().map(|d| DashboardPrSize {
+        additions: d.additions,
+        deletions: d.deletions,
+        changed_files: d.changed_files,
+        commits: d.commits,
+        bucket: size_bucket(d.additions + d.deletions, size_thresholds),
+    });
 
     Ok(DashboardPR {
         id: item.id,
@@ -848,15 +1873,19 @@ fn finish_enrich(
         title: item.title.clone(),
         url: item.html_url.clone(),
         repo: repo.to_string(),
+        account: account.to_string(),
         author: DashboardAuthor {
             login: item.user.login.clone(),
             avatar_url: item.user.avatar_url.clone(),
+            is_bot: item.user.is_bot(),
         },
         turn_status,
         turn_debug_info: Some(debug_info),
         is_draft: item.draft,
         created_at: item.created_at.clone(),
         updated_at: item.updated_at.clone(),
+        turn_since: Some(turn_since),
+        waiting_for,
         labels: item
             .labels
             .iter()
@@ -865,52 +1894,231 @@ fn finish_enrich(
                 color: l.color.clone(),
             })
             .collect(),
+        reviewers,
         review_summary,
+        size,
+        mergeable_state: mergeable_state.map(str::to_string),
+        ci_status: *ci_status,
+        skeleton: false,
     })
 }
 
+/// A placeholder PR from a search item, shown until enrichment finishes.
+/// Review requests are guessed to be my turn and my PRs their turn.
+fn skeleton_pr(item: &GitHubSearchItem, account: &str, turn_status: TurnStatus) -> DashboardPR {
+    DashboardPR {
+        id: item.id,
+        number: item.number,
+        title: item.title.clone(),
+        url: item.html_url.clone(),
+        repo: parse_repo(&item.repository_url),
+        account: account.to_string(),
+        author: DashboardAuthor {
+            login: item.user.login.clone(),
+            avatar_url: item.user.avatar_url.clone(),
+            is_bot: item.user.is_bot(),
+        },
+        turn_status,
+        turn_debug_info: None,
+        is_draft: item.draft,
+        created_at: item.created_at.clone(),
+        updated_at: item.updated_at.clone(),
+        turn_since: None,
+        waiting_for: None,
+        labels: item
+            .labels
+            .iter()
+            .map(|l| DashboardLabel {
+                name: l.name.clone(),
+                color: l.color.clone(),
+            })
+            .collect(),
+        reviewers: Vec::new(),
+        review_summary: String::new(),
+        size: None,
+        mergeable_state: None,
+        ci_status: None,
+        skeleton: true,
+    }
+}
+
+fn size_bucket(lines_changed: u64, thresholds: &SizeThresholds) -> SizeBucket {
+    if lines_changed <= thresholds.xs {
+        SizeBucket::XS
+    } else if lines_changed <= thresholds.s {
+        SizeBucket::S
+    } else if lines_changed <= thresholds.m {
+        SizeBucket::M
+    } else if lines_changed <= thresholds.l {
+        SizeBucket::L
+    } else {
+        SizeBucket::XL
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Sort
 // ---------------------------------------------------------------------------
 
-fn sort_prs(prs: &mut [DashboardPR]) {
+fn sort_prs(
+    prs: &mut [DashboardPR],
+    keys: &[SortKey],
+    label_priority: &[String],
+    bots_last: bool,
+) {
+    let priority: Vec<String> = label_priority.iter().map(|l| l.to_lowercase()).collect();
     prs.sort_by(|a, b| {
-        // "my-turn" first
-        if a.turn_status != b.turn_status {
-            return if a.turn_status == TurnStatus::MyTurn {
-                std::cmp::Ordering::Less
-            } else {
-                std::cmp::Ordering::Greater
-            };
-        }
-        // Then by most recently updated (descending)
-        b.updated_at.cmp(&a.updated_at)
+        let bot_order = if bots_last {
+            a.author.is_bot.cmp(&b.author.is_bot)
+        } else {
+            Ordering::Equal
+        };
+        std::iter::once(bot_order)
+            .chain(keys.iter().map(|key| compare_by_key(*key, a, b, &priority)))
+            .find(|ord| *ord != Ordering::Equal)
+            // Fall back to most recently updated so ties stay stable across polls
+            .unwrap_or_else(|| b.updated_at.cmp(&a.updated_at))
     });
 }
 
+fn compare_by_key(
+    key: SortKey,
+    a: &DashboardPR,
+    b: &DashboardPR,
+    priority: &[String],
+) -> Ordering {
+    match key {
+        SortKey::TurnStatus => turn_rank(&a.turn_status).cmp(&turn_rank(&b.turn_status)),
+        // ISO-8601 strings compare chronologically
+        SortKey::RecentlyUpdated => b.updated_at.cmp(&a.updated_at),
+        SortKey::OldestWaiting => waiting_since(a).cmp(waiting_since(b)),
+        SortKey::Repo => a.repo.to_lowercase().cmp(&b.repo.to_lowercase()),
+        SortKey::LabelPriority => label_rank(a, priority).cmp(&label_rank(b, priority)),
+        SortKey::DraftLast => a.is_draft.cmp(&b.is_draft),
+        SortKey::Size => lines_changed(a).cmp(&lines_changed(b)),
+    }
+}
+
+fn lines_changed(pr: &DashboardPR) -> u64 {
+    pr.size
+        .as_ref()
+        .map(|size| size.additions + size.deletions)
+        .unwrap_or(u64::MAX)
+}
+
+fn waiting_since(pr: &DashboardPR) -> &str {
+    pr.turn_since.as_deref().unwrap_or(&pr.updated_at)
+}
+
+fn turn_rank(status: &TurnStatus) -> u8 {
+    match status {
+        TurnStatus::MyTurn => 0,
+        TurnStatus::TheirTurn => 1,
+    }
+}
+
+/// Index of the highest-priority label on the PR; unlisted PRs sort last.
+fn label_rank(pr: &DashboardPR, priority: &[String]) -> usize {
+    pr.labels
+        .iter()
+        .filter_map(|label| {
+            let name = label.name.to_lowercase();
+            priority.iter().position(|p| *p == name)
+        })
+        .min()
+        .unwrap_or(priority.len())
+}
+
+fn sort_dashboard_response(
+    response: &mut DashboardResponse,
+    sort: &SortConfig,
+    bot_mode: BotPrMode,
+) {
+    let bots_last = bot_mode == BotPrMode::Deprioritize;
+    sort_prs(
+        &mut response.my_prs,
+        sort.keys_for("my-prs"),
+        &sort.label_priority,
+        bots_last,
+    );
+    sort_prs(
+        &mut response.review_requests,
+        sort.keys_for("review-requests"),
+        &sort.label_priority,
+        bots_last,
+    );
+    sort_prs(
+        &mut response.bot_prs,
+        sort.keys_for("review-requests"),
+        &sort.label_priority,
+        false,
+    );
+}
+
 // ---------------------------------------------------------------------------
-// Tauri commands
+// Filters
 // ---------------------------------------------------------------------------
 
-#[tauri::command]
-pub async fn validate_pat(pat: String) -> Result<GitHubAuthenticatedUser, String> {
-    let client = reqwest::Client::new();
-    let user = fetch_authenticated_user(&client, &pat).await.map_err(|e| {
-        if e.starts_with("RATE_LIMITED:") {
-            e.replacen("RATE_LIMITED: ", "", 1)
-        } else {
-            format!("Invalid Personal Access Token or GitHub API error: {}", e)
-        }
-    })?;
-    Ok(user)
+/// Whether the filters and sections for the PR's repository keep it on the
+/// dashboard. Checked before enrichment so hidden PRs cost no API calls.
+fn passes_rules(item: &GitHubSearchItem, section: &str, rules: &Rules) -> bool {
+    let repo_rules = rules.for_repo(&parse_repo(&item.repository_url));
+    let filters = &repo_rules.filters;
+    let matches_any = |names: &[String], name: &str| {
+        names.iter().any(|n| n.eq_ignore_ascii_case(name))
+    };
+
+    let hidden = filters.hide
+        || (filters.hide_drafts && item.draft)
+        || matches_any(&filters.hide_authors, &item.user.login)
+        || item
+            .labels
+            .iter()
+            .any(|label| matches_any(&filters.hide_labels, &label.name));
+    repo_rules.sections.shows(section) && !hidden
 }
 
-#[tauri::command]
-pub async fn fetch_dashboard(pat: String) -> Result<DashboardResponse, String> {
-    let client = reqwest::Client::new();
+// ---------------------------------------------------------------------------
+// Per-account fetch
+// ---------------------------------------------------------------------------
+
+/// Partial results reported while a dashboard fetch is running.
+pub enum DashboardProgress {
+    /// One account's search results as skeleton PRs, bot PRs split out when
+    /// `bot_prs` is "group"
+    Searched {
+        my_prs: Vec<DashboardPR>,
+        review_requests: Vec<DashboardPR>,
+        bot_prs: Vec<DashboardPR>,
+    },
+    /// One PR finished enrichment
+    Enriched(Box<DashboardPR>),
+}
+
+/// Receives `DashboardProgress` from the concurrently running account and
+/// enrichment futures.
+pub type ProgressFn<'a> = &'a (dyn Fn(DashboardProgress) + Send + Sync);
+
+/// Everything one account contributes to the dashboard, before merging.
+struct AccountDashboard {
+    github_username: String,
+    token: TokenInfo,
+    my_prs: Vec<DashboardPR>,
+    review_requests: Vec<DashboardPR>,
+    warnings: Vec<DashboardWarning>,
+    stats: RefreshStats,
+}
 
+async fn fetch_account_dashboard(
+    api: GitHubApi<'_>,
+    account: &str,
+    bot_mode: BotPrMode,
+    size_thresholds: &SizeThresholds,
+    rules: &Rules,
+    progress: ProgressFn<'_>,
+) -> Result<AccountDashboard, String> {
     // 1. Resolve the authenticated user
-    let gh_user = fetch_authenticated_user(&client, &pat).await.map_err(|e| {
+    let token = fetch_token_info(api).await.map_err(|e| {
         if e.starts_with("RATE_LIMITED:") {
             e.replacen("RATE_LIMITED: ", "", 1)
         } else {
@@ -920,19 +2128,47 @@ pub async fn fetch_dashboard(pat: String) -> Result<DashboardResponse, String> {
             )
         }
     })?;
-    let github_username = gh_user.login;
+    let github_username = token.user.login.clone();
 
     // 2. Fetch PRs from GitHub — three parallel searches
-    let my_prs_fut = fetch_my_prs(&client, &github_username, &pat);
-    let review_requests_fut = fetch_review_requests(&client, &github_username, &pat);
-    let reviewed_by_fut = fetch_reviewed_by(&client, &github_username, &pat);
+    let my_prs_fut = fetch_my_prs(api, &github_username);
+    let review_requests_fut = fetch_review_requests(api, &github_username);
+    let reviewed_by_fut = fetch_reviewed_by(api, &github_username);
 
     let (my_pr_result, rr_result, rb_result) =
         tokio::join!(my_prs_fut, review_requests_fut, reviewed_by_fut);
 
-    let my_pr_items = my_pr_result.map_err(|e| format_search_error(&e))?;
-    let review_request_items = rr_result.map_err(|e| format_search_error(&e))?;
-    let reviewed_by_items = rb_result.map_err(|e| format_search_error(&e))?;
+    let my_pr_search = my_pr_result.map_err(|e| format_search_error(&e))?;
+    let rr_search = rr_result.map_err(|e| format_search_error(&e))?;
+    let rb_search = rb_result.map_err(|e| format_search_error(&e))?;
+
+    let mut warnings: Vec<DashboardWarning> = Vec::new();
+    let sso_hidden_orgs: HashSet<&String> = my_pr_search
+        .sso_hidden_orgs
+        .iter()
+        .chain(&rr_search.sso_hidden_orgs)
+        .chain(&rb_search.sso_hidden_orgs)
+        .collect();
+    if !sso_hidden_orgs.is_empty() {
+        warnings.push(DashboardWarning {
+            kind: DashboardWarningKind::SsoRequired,
+            account: account.to_string(),
+            message: format!(
+                "Search results from {} organization(s) are hidden: authorize your token \
+                 for their SAML SSO.",
+                sso_hidden_orgs.len()
+            ),
+            url: None,
+        });
+    }
+
+    let my_pr_items: Vec<GitHubSearchItem> = my_pr_search
+        .items
+        .into_iter()
+        .filter(|item| passes_rules(item, "my-prs", rules))
+        .collect();
+    let review_request_items = rr_search.items;
+    let reviewed_by_items = rb_search.items;
 
     // 3. Track which PRs came from review-requested search
     let review_requested_ids: HashSet<u64> =
@@ -942,21 +2178,73 @@ pub async fn fetch_dashboard(pat: String) -> Result<DashboardResponse, String> {
     let mut review_items_map: HashMap<u64, GitHubSearchItem> = HashMap::new();
     for item in review_request_items
         .into_iter()
-        .chain(reviewed_by_items.into_iter())
+        .chain(reviewed_by_items)
     {
         review_items_map.entry(item.id).or_insert(item);
     }
 
-    // Remove PRs authored by the user (no self-review)
+    // Remove PRs authored by the user (no self-review), and bot PRs when hidden
     let deduped_review_items: Vec<GitHubSearchItem> = review_items_map
         .into_values()
         .filter(|item| item.user.login.to_lowercase() != github_username.to_lowercase())
+        .filter(|item| !(bot_mode == BotPrMode::Hide && item.user.is_bot()))
+        .filter(|item| passes_rules(item, "review-requests", rules))
         .collect();
 
-    // 5. Enrich each PR with review details — parallel enrichment
+    // Show the search results straight away, enrichment takes much longer
+    let mut skeleton_reviews: Vec<DashboardPR> = deduped_review_items
+        .iter()
+        .map(|item| {
+            let turn = if review_requested_ids.contains(&item.id) {
+                TurnStatus::MyTurn
+            } else {
+                TurnStatus::TheirTurn
+            };
+            skeleton_pr(item, account, turn)
+        })
+        .collect();
+    let skeleton_bots = if bot_mode == BotPrMode::Group {
+        let (bots, humans) = skeleton_reviews
+            .into_iter()
+            .partition(|pr| pr.author.is_bot);
+        skeleton_reviews = humans;
+        bots
+    } else {
+        Vec::new()
+    };
+    progress(DashboardProgress::Searched {
+        my_prs: my_pr_items
+            .iter()
+            .map(|item| skeleton_pr(item, account, TurnStatus::TheirTurn))
+            .collect(),
+        review_requests: skeleton_reviews,
+        bot_prs: skeleton_bots,
+    });
+
+    // 5. Enrich each PR with review details — parallel enrichment, each PR
+    // reported as it completes
+    let report = |result: Result<(DashboardPR, bool), String>| {
+        if let Ok((pr, _)) = &result {
+            progress(DashboardProgress::Enriched(Box::new(pr.clone())));
+        }
+        result
+    };
     let my_pr_futures: Vec<_> = my_pr_items
         .iter()
-        .map(|item| enrich_pr(&client, item, &pat, "my-prs", &github_username, false))
+        .map(|item| {
+            enrich_pr(
+                api,
+                account,
+                item,
+                "my-prs",
+                &github_username,
+                false,
+                false,
+                size_thresholds,
+                rules,
+            )
+            .map(report)
+        })
         .collect();
 
     let review_futures: Vec<_> = deduped_review_items
@@ -964,47 +2252,346 @@ pub async fn fetch_dashboard(pat: String) -> Result<DashboardResponse, String> {
         .map(|item| {
             let is_rr = review_requested_ids.contains(&item.id);
             enrich_pr(
-                &client,
+                api,
+                account,
                 item,
-                &pat,
                 "review-requests",
                 &github_username,
                 is_rr,
+                false,
+                size_thresholds,
+                rules,
             )
+            .map(report)
         })
         .collect();
 
     let my_prs_results = futures::future::join_all(my_pr_futures).await;
     let review_results = futures::future::join_all(review_futures).await;
 
-    let mut my_prs: Vec<DashboardPR> = Vec::new();
-    for result in my_prs_results {
+    // PRs in organizations that enforce SAML SSO the token isn't authorized
+    // for are skipped with one warning per organization
+    let mut sso_urls: HashSet<String> = HashSet::new();
+    let mut stats = RefreshStats::default();
+    let mut collect = |results: Vec<Result<(DashboardPR, bool), String>>| {
+        let mut prs: Vec<DashboardPR> = Vec::new();
+        for result in results {
+            match result {
+                Ok((pr, refetched)) => {
+                    if refetched {
+                        stats.enriched += 1;
+                    } else {
+                        stats.reused += 1;
+                    }
+                    prs.push(pr);
+                }
+                Err(e) => match SsoRequired::from_error(&e) {
+                    Some(sso) => {
+                        let key = sso.organization.clone().unwrap_or_else(|| sso.url.clone());
+                        if sso_urls.insert(key) {
+                            warnings.push(DashboardWarning {
+                                kind: DashboardWarningKind::SsoRequired,
+                                account: account.to_string(),
+                                message: sso.message(),
+                                url: Some(sso.url),
+                            });
+                        }
+                    }
+                    None => return Err(format_enrichment_error(&e)),
+                },
+            }
+        }
+        Ok(prs)
+    };
+    let my_prs = collect(my_prs_results)?;
+    let review_requests = collect(review_results)?;
+
+    let current_ids: HashSet<u64> = my_pr_items
+        .iter()
+        .chain(&deduped_review_items)
+        .map(|item| item.id)
+        .collect();
+    prune_enrichment_cache(account, &current_ids);
+
+    Ok(AccountDashboard {
+        github_username,
+        token,
+        my_prs,
+        review_requests,
+        warnings,
+        stats,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Single-PR refresh
+// ---------------------------------------------------------------------------
+
+/// Result of refreshing one PR.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum PrRefresh {
+    Open { pr: Box<DashboardPR> },
+    /// Tombstone for a PR closed or merged since the last refresh
+    Closed { url: String, merged: bool },
+}
+
+/// Re-enrich one PR already on the dashboard with the account that fetched
+/// it, skipping the searches and the enrichment cache. `section` and
+/// `my_username` come from the dashboard it is on.
+pub async fn fetch_single_pr(
+    existing: &DashboardPR,
+    section: &str,
+    my_username: &str,
+) -> Result<PrRefresh, String> {
+    let app_config = config::load_config()?;
+    let account = app_config
+        .effective_accounts()
+        .into_iter()
+        .find(|a| a.label == existing.account)
+        .ok_or_else(|| format!("Account \"{}\" is no longer configured", existing.account))?;
+    let client = reqwest::Client::new();
+    let api = GitHubApi::new(&client, &account.api_base_url, &account.token);
+
+    // The issue endpoint returns the same shape as a search item
+    let url = format!(
+        "{}/repos/{}/issues/{}",
+        api.base_url, existing.repo, existing.number
+    );
+    let item: GitHubSearchItem = github_fetch(api, &url)
+        .await
+        .map_err(|e| format_enrichment_error(&e))?;
+    if item.state == "closed" {
+        let merged = item
+            .pull_request
+            .as_ref()
+            .is_some_and(|pr| pr.merged_at.is_some());
+        forget_cached_details(&account.label, item.id);
+        return Ok(PrRefresh::Closed {
+            url: existing.url.clone(),
+            merged,
+        });
+    }
+
+    // The cache entry is only replaced once the new details are in, so a
+    // failed refresh keeps it
+    // Without a cache entry (expired, or cleared by a config change) fall
+    // back to the dashboard: a review request is only the user's turn when
+    // they were asked to review, individually or through a team
+    let is_review_requested = cached_review_requested(&account.label, item.id)
+        .unwrap_or(section == "review-requests" && existing.turn_status == TurnStatus::MyTurn);
+    let (pr, _) = enrich_pr(
+        api,
+        &account.label,
+        &item,
+        section,
+        my_username,
+        is_review_requested,
+        true,
+        &app_config.size_thresholds,
+        &app_config.rules,
+    )
+    .await
+    .map_err(|e| format_enrichment_error(&e))?;
+    Ok(PrRefresh::Open { pr: Box::new(pr) })
+}
+
+/// Re-sort a dashboard in place with the current config, e.g. after one PR
+/// in it was replaced.
+pub fn resort_dashboard(dashboard: &mut DashboardResponse) -> Result<(), String> {
+    let app_config = config::effective_config()?;
+    sort_dashboard_response(dashboard, &app_config.sort, app_config.bot_prs);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn validate_pat(
+    pat: String,
+    api_base_url: Option<String>,
+) -> Result<TokenInfo, String> {
+    let client = reqwest::Client::new();
+    let base_url = api_base_url.unwrap_or_else(|| GITHUB_API.to_string());
+    let api = GitHubApi::new(&client, &base_url, &pat);
+    fetch_token_info(api).await.map_err(|e| {
+        if e.starts_with("RATE_LIMITED:") {
+            e.replacen("RATE_LIMITED: ", "", 1)
+        } else {
+            format!("Invalid Personal Access Token or GitHub API error: {}", e)
+        }
+    })
+}
+
+/// Re-sort an already fetched dashboard without hitting GitHub, so the UI can
+/// switch ordering instantly.
+#[tauri::command]
+pub fn sort_dashboard(
+    dashboard: DashboardResponse,
+    sort: Option<SortConfig>,
+) -> Result<DashboardResponse, String> {
+    let app_config = config::effective_config()?;
+    let sort = sort.unwrap_or(app_config.sort);
+    let mut dashboard = dashboard;
+    sort_dashboard_response(&mut dashboard, &sort, app_config.bot_prs);
+    Ok(dashboard)
+}
+
+// ---------------------------------------------------------------------------
+// Dashboard fetch
+// ---------------------------------------------------------------------------
+
+/// Fetch the dashboard for every configured account concurrently and merge
+/// the results in one response, outside the poller's schedule and without
+/// diffing. `sort` overrides the configured order for this response only.
+/// Tokens never leave the backend.
+#[tauri::command]
+pub async fn fetch_dashboard(sort: Option<SortConfig>) -> Result<DashboardResponse, String> {
+    fetch_dashboard_streaming(sort, &|_| {}).await
+}
+
+/// `fetch_dashboard`, reporting skeleton PRs after the searches and each PR
+/// as its enrichment completes. The returned response is the final
+/// dashboard, sorted by `sort` or else the configured order.
+pub async fn fetch_dashboard_streaming(
+    sort: Option<SortConfig>,
+    progress: ProgressFn<'_>,
+) -> Result<DashboardResponse, String> {
+    let client = reqwest::Client::new();
+    let app_config = config::load_config()?;
+    let sort = &sort.unwrap_or_else(|| app_config.sort.clone());
+    let size_thresholds = &app_config.size_thresholds;
+    let bot_mode = app_config.bot_prs;
+    let rules = &app_config.rules;
+
+    let accounts = app_config.effective_accounts();
+    if accounts.is_empty() {
+        return Err("No GitHub account configured".to_string());
+    }
+
+    let account_futures: Vec<_> = accounts
+        .iter()
+        .map(|account| {
+            let client = &client;
+            async move {
+                let api = GitHubApi::new(client, &account.api_base_url, &account.token);
+                fetch_account_dashboard(
+                    api,
+                    &account.label,
+                    bot_mode,
+                    size_thresholds,
+                    rules,
+                    progress,
+                )
+                .await
+            }
+        })
+        .collect();
+    let account_results = futures::future::join_all(account_futures).await;
+
+    // Merge accounts in config order. The same PR seen by several accounts is
+    // kept once, and a PR that is one account's own PR wins over a review
+    // request seen by another.
+    let now = unix_now();
+    let mut statuses: Vec<DashboardAccountStatus> = Vec::new();
+    let mut warnings: Vec<DashboardWarning> = Vec::new();
+    let mut dashboards: Vec<AccountDashboard> = Vec::new();
+    let mut refresh_stats = RefreshStats::default();
+    for (account, result) in accounts.iter().zip(account_results) {
         match result {
-            Ok(pr) => my_prs.push(pr),
-            Err(e) => return Err(format_enrichment_error(&e)),
+            Ok(mut dashboard) => {
+                warnings.append(&mut dashboard.warnings);
+                refresh_stats.enriched += dashboard.stats.enriched;
+                refresh_stats.reused += dashboard.stats.reused;
+                warnings.extend(token_warnings(
+                    &account.label,
+                    &dashboard.token,
+                    app_config.token_expiry_warning_days,
+                    now,
+                ));
+                statuses.push(DashboardAccountStatus {
+                    label: account.label.clone(),
+                    login: Some(dashboard.github_username.clone()),
+                    error: None,
+                    token_expires_at: dashboard.token.expires_at.clone(),
+                });
+                dashboards.push(dashboard);
+            }
+            Err(e) => statuses.push(DashboardAccountStatus {
+                label: account.label.clone(),
+                login: None,
+                error: Some(e),
+                token_expires_at: None,
+            }),
         }
     }
 
+    if dashboards.is_empty() {
+        let errors: Vec<String> = statuses
+            .into_iter()
+            .filter_map(|status| status.error.map(|e| (status.label, e)))
+            .map(|(label, e)| {
+                if accounts.len() == 1 {
+                    e
+                } else {
+                    format!("{}: {}", label, e)
+                }
+            })
+            .collect();
+        return Err(errors.join("; "));
+    }
+
+    let github_username = dashboards[0].github_username.clone();
+    let mut seen_urls: HashSet<String> = HashSet::new();
+    let mut my_prs: Vec<DashboardPR> = Vec::new();
     let mut review_requests: Vec<DashboardPR> = Vec::new();
-    for result in review_results {
-        match result {
-            Ok(pr) => review_requests.push(pr),
-            Err(e) => return Err(format_enrichment_error(&e)),
+    let (account_my_prs, account_review_requests): (Vec<_>, Vec<_>) = dashboards
+        .into_iter()
+        .map(|d| (d.my_prs, d.review_requests))
+        .unzip();
+    for pr in account_my_prs.into_iter().flatten() {
+        if seen_urls.insert(pr.url.clone()) {
+            my_prs.push(pr);
+        }
+    }
+    for pr in account_review_requests.into_iter().flatten() {
+        if seen_urls.insert(pr.url.clone()) {
+            review_requests.push(pr);
         }
     }
 
-    // 6. Sort
-    sort_prs(&mut my_prs);
-    sort_prs(&mut review_requests);
+    let bot_prs: Vec<DashboardPR> = if bot_mode == BotPrMode::Group {
+        let (bots, humans): (Vec<DashboardPR>, Vec<DashboardPR>) = review_requests
+            .into_iter()
+            .partition(|pr| pr.author.is_bot);
+        review_requests = humans;
+        bots
+    } else {
+        Vec::new()
+    };
 
     let fetched_at = chrono_now_iso();
 
-    Ok(DashboardResponse {
+    let mut response = DashboardResponse {
         my_prs,
         review_requests,
+        bot_prs,
         github_username,
+        accounts: statuses,
+        warnings,
+        changes: Vec::new(),
         fetched_at,
-    })
+        stale: false,
+        refresh_stats,
+        generation: 0,
+    };
+
+    // 6. Sort
+    sort_dashboard_response(&mut response, sort, bot_mode);
+
+    Ok(response)
 }
 
 // ---------------------------------------------------------------------------
@@ -1014,6 +2601,8 @@ pub async fn fetch_dashboard(pat: String) -> Result<DashboardResponse, String> {
 fn format_search_error(msg: &str) -> String {
     if msg.starts_with("RATE_LIMITED:") {
         msg.replacen("RATE_LIMITED: ", "", 1)
+    } else if msg.starts_with(SSO_REQUIRED_PREFIX) {
+        msg.to_string()
     } else {
         format!("GitHub search failed: {}", msg)
     }
@@ -1022,47 +2611,303 @@ fn format_search_error(msg: &str) -> String {
 fn format_enrichment_error(msg: &str) -> String {
     if msg.starts_with("RATE_LIMITED:") {
         msg.replacen("RATE_LIMITED: ", "", 1)
+    } else if msg.starts_with(SSO_REQUIRED_PREFIX) {
+        msg.to_string()
     } else {
         format!("GitHub PR enrichment failed: {}", msg)
     }
 }
 
-/// Produce an ISO-8601 timestamp for "now" without pulling in the chrono crate.
-/// Uses `std::time::SystemTime` for a lightweight solution.
-fn chrono_now_iso() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let duration = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default();
-    let secs = duration.as_secs();
-    // Convert to a simple UTC ISO-8601 string
-    let days = secs / 86400;
-    let time_secs = secs % 86400;
-    let hours = time_secs / 3600;
-    let minutes = (time_secs % 3600) / 60;
-    let seconds = time_secs % 60;
+/// The current time as an ISO-8601 UTC timestamp.
+pub fn chrono_now_iso() -> String {
+    format_iso(Utc::now())
+}
 
-    // Calculate year/month/day from days since epoch (1970-01-01)
-    let (year, month, day) = days_to_date(days);
+/// The "2024-05-01T12:34:56Z" form GitHub uses.
+fn format_iso(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
 
-    format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-        year, month, day, hours, minutes, seconds
-    )
+/// Current time as seconds since the UNIX epoch.
+pub fn unix_now() -> u64 {
+    u64::try_from(Utc::now().timestamp()).unwrap_or_default()
 }
 
-/// Convert days since 1970-01-01 to (year, month, day).
-fn days_to_date(days_since_epoch: u64) -> (u64, u64, u64) {
-    // Algorithm from Howard Hinnant's chrono-compatible date library
-    let z = days_since_epoch + 719468;
-    let era = z / 146097;
-    let doe = z - era * 146097;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 };
-    let y = if m <= 2 { y + 1 } else { y };
-    (y, m, d)
+/// Parse a GitHub timestamp ("2024-05-01T12:34:56Z") into seconds since the
+/// UNIX epoch.
+pub fn parse_iso_timestamp(ts: &str) -> Option<u64> {
+    let time = DateTime::parse_from_rfc3339(ts).ok()?;
+    u64::try_from(time.timestamp()).ok()
+}
+
+/// "45s", "12m", "3h 20m", "2d 4h"
+fn humanize_duration(secs: u64) -> String {
+    let days = secs / 86400;
+    let hours = (secs % 86400) / 3600;
+    let minutes = (secs % 3600) / 60;
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn timeline(events: serde_json::Value) -> Vec<GitHubTimelineEvent> {
+        serde_json::from_value(events).unwrap()
+    }
+
+    fn user(login: &str) -> serde_json::Value {
+        json!({ "login": login, "avatar_url": "", "id": 1 })
+    }
+
+    fn reviewed(login: &str, at: &str) -> serde_json::Value {
+        json!({ "event": "reviewed", "submitted_at": at, "user": user(login) })
+    }
+
+    fn committed(at: &str) -> serde_json::Value {
+        json!({ "event": "committed", "committer": { "date": at } })
+    }
+
+    fn review_requested(login: &str, at: &str) -> serde_json::Value {
+        json!({ "event": "review_requested", "created_at": at, "requested_reviewer": user(login) })
+    }
+
+    const CREATED: &str = "2024-05-01T00:00:00Z";
+
+    #[test]
+    fn turn_since_is_start_of_trailing_run() {
+        let events = timeline(json!([
+            committed("2024-05-01T01:00:00Z"),
+            reviewed("alice", "2024-05-01T02:00:00Z"),
+            committed("2024-05-01T03:00:00Z"),
+            review_requested("alice", "2024-05-01T04:00:00Z"),
+        ]));
+        let since = determine_turn_since(
+            &events,
+            "my-prs",
+            &TurnStatus::TheirTurn,
+            "me",
+            "me",
+            CREATED,
+        );
+        assert_eq!(since, "2024-05-01T03:00:00Z");
+    }
+
+    #[test]
+    fn turn_since_is_latest_event_when_it_points_the_other_way() {
+        // My turn because of, say, a merge conflict, though the last event
+        // handed the ball to the reviewers
+        let events = timeline(json!([
+            reviewed("alice", "2024-05-01T02:00:00Z"),
+            committed("2024-05-01T03:00:00Z"),
+        ]));
+        let since =
+            determine_turn_since(&events, "my-prs", &TurnStatus::MyTurn, "me", "me", CREATED);
+        assert_eq!(since, "2024-05-01T03:00:00Z");
+    }
+
+    #[test]
+    fn turn_since_orders_events_by_time() {
+        // Commit dates can predate events listed before them
+        let events = timeline(json!([
+            reviewed("alice", "2024-05-01T02:00:00Z"),
+            committed("2024-05-01T01:00:00Z"),
+        ]));
+        let since =
+            determine_turn_since(&events, "my-prs", &TurnStatus::MyTurn, "me", "me", CREATED);
+        assert_eq!(since, "2024-05-01T02:00:00Z");
+    }
+
+    #[test]
+    fn turn_since_ignores_other_reviewers_on_review_requests() {
+        let events = timeline(json!([
+            review_requested("me", "2024-05-01T01:00:00Z"),
+            reviewed("bob", "2024-05-01T02:00:00Z"),
+        ]));
+        let since = determine_turn_since(
+            &events,
+            "review-requests",
+            &TurnStatus::MyTurn,
+            "author",
+            "me",
+            CREATED,
+        );
+        assert_eq!(since, "2024-05-01T01:00:00Z");
+    }
+
+    #[test]
+    fn turn_since_falls_back_to_created_at() {
+        let events =
+            timeline(json!([{ "event": "labeled", "created_at": "2024-05-02T00:00:00Z" }]));
+        let since =
+            determine_turn_since(&events, "my-prs", &TurnStatus::MyTurn, "me", "me", CREATED);
+        assert_eq!(since, CREATED);
+    }
+
+    fn pr(number: u64, updated_at: &str) -> DashboardPR {
+        DashboardPR {
+            id: number,
+            number,
+            title: format!("PR {}", number),
+            url: format!("https://github.com/org/repo/pull/{}", number),
+            repo: "org/repo".to_string(),
+            account: "github.com".to_string(),
+            author: DashboardAuthor {
+                login: "author".to_string(),
+                avatar_url: String::new(),
+                is_bot: false,
+            },
+            turn_status: TurnStatus::TheirTurn,
+            turn_debug_info: None,
+            is_draft: false,
+            created_at: CREATED.to_string(),
+            updated_at: updated_at.to_string(),
+            turn_since: None,
+            waiting_for: None,
+            labels: Vec::new(),
+            reviewers: Vec::new(),
+            review_summary: String::new(),
+            size: None,
+            mergeable_state: None,
+            ci_status: None,
+            skeleton: false,
+        }
+    }
+
+    fn numbers(prs: &[DashboardPR]) -> Vec<u64> {
+        prs.iter().map(|pr| pr.number).collect()
+    }
+
+    #[test]
+    fn sorts_by_turn_then_recently_updated() {
+        let mut prs = vec![
+            pr(1, "2024-05-01T01:00:00Z"),
+            pr(2, "2024-05-01T02:00:00Z"),
+            pr(3, "2024-05-01T03:00:00Z"),
+        ];
+        prs[0].turn_status = TurnStatus::MyTurn;
+        sort_prs(
+            &mut prs,
+            &[SortKey::TurnStatus, SortKey::RecentlyUpdated],
+            &[],
+            false,
+        );
+        assert_eq!(numbers(&prs), [1, 3, 2]);
+    }
+
+    #[test]
+    fn oldest_waiting_uses_turn_since_before_updated_at() {
+        let mut prs = vec![pr(1, "2024-05-01T01:00:00Z"), pr(2, "2024-05-01T05:00:00Z")];
+        prs[1].turn_since = Some("2024-05-01T00:30:00Z".to_string());
+        sort_prs(&mut prs, &[SortKey::OldestWaiting], &[], false);
+        assert_eq!(numbers(&prs), [2, 1]);
+    }
+
+    #[test]
+    fn label_priority_is_case_insensitive_and_unlisted_last() {
+        let label = |name: &str| DashboardLabel {
+            name: name.to_string(),
+            color: String::new(),
+        };
+        let mut prs = vec![
+            pr(1, "2024-05-01T03:00:00Z"),
+            pr(2, "2024-05-01T02:00:00Z"),
+            pr(3, "2024-05-01T01:00:00Z"),
+        ];
+        prs[1].labels.push(label("Bug"));
+        prs[2].labels.push(label("urgent"));
+        let priority = ["URGENT".to_string(), "bug".to_string()];
+        sort_prs(&mut prs, &[SortKey::LabelPriority], &priority, false);
+        assert_eq!(numbers(&prs), [3, 2, 1]);
+    }
+
+    #[test]
+    fn unknown_size_sorts_last() {
+        let size = |lines: u64| DashboardPrSize {
+            additions: lines,
+            deletions: 0,
+            changed_files: 1,
+            commits: 1,
+            bucket: SizeBucket::S,
+        };
+        let mut prs = vec![
+            pr(1, "2024-05-01T03:00:00Z"),
+            pr(2, "2024-05-01T02:00:00Z"),
+            pr(3, "2024-05-01T01:00:00Z"),
+        ];
+        prs[1].size = Some(size(200));
+        prs[2].size = Some(size(10));
+        sort_prs(&mut prs, &[SortKey::Size], &[], false);
+        assert_eq!(numbers(&prs), [3, 2, 1]);
+    }
+
+    #[test]
+    fn bots_last_comes_before_every_key() {
+        let mut prs = vec![pr(1, "2024-05-01T01:00:00Z"), pr(2, "2024-05-01T02:00:00Z")];
+        prs[0].turn_status = TurnStatus::MyTurn;
+        prs[0].author.is_bot = true;
+        sort_prs(&mut prs, &[SortKey::TurnStatus], &[], true);
+        assert_eq!(numbers(&prs), [2, 1]);
+    }
+
+    #[test]
+    fn ties_fall_back_to_recently_updated() {
+        let mut prs = vec![pr(1, "2024-05-01T01:00:00Z"), pr(2, "2024-05-01T02:00:00Z")];
+        sort_prs(&mut prs, &[SortKey::Repo, SortKey::DraftLast], &[], false);
+        assert_eq!(numbers(&prs), [2, 1]);
+    }
+
+    #[test]
+    fn parses_sso_required_header() {
+        let sso = SsoRequired::from_header(
+            "required; url=https://github.com/orgs/acme/sso?authorization_request=abc",
+        )
+        .unwrap();
+        assert_eq!(sso.organization.as_deref(), Some("acme"));
+        assert_eq!(
+            sso.url,
+            "https://github.com/orgs/acme/sso?authorization_request=abc"
+        );
+    }
+
+    #[test]
+    fn sso_header_without_required_or_url_is_ignored() {
+        assert!(SsoRequired::from_header("partial-results; organizations=1").is_none());
+        assert!(SsoRequired::from_header("required").is_none());
+    }
+
+    #[test]
+    fn sso_error_round_trips() {
+        let sso = SsoRequired {
+            organization: None,
+            url: "https://ghes.example.com/sso".to_string(),
+        };
+        let parsed = SsoRequired::from_error(&sso.to_error()).unwrap();
+        assert_eq!(parsed.organization, None);
+        assert_eq!(parsed.url, sso.url);
+        assert!(SsoRequired::from_error("Bad credentials").is_none());
+    }
+
+    #[test]
+    fn parses_sso_partial_results_header() {
+        let mut headers = HeaderMap::new();
+        assert!(parse_sso_partial_results(&headers).is_empty());
+        headers.insert(
+            "x-github-sso",
+            HeaderValue::from_static("partial-results; organizations=21955855, 20582480"),
+        );
+        assert_eq!(
+            parse_sso_partial_results(&headers),
+            ["21955855", "20582480"]
+        );
+    }
 }
@@ -0,0 +1,534 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::config::{self, AppConfig, RepoRules, Rules};
+
+/// The user's hand-edited config, next to config.json.
+const USER_CONFIG_FILE: &str = "config.toml";
+
+/// `AppConfig` fields the TOML files may set. Accounts, tokens and the secret
+/// store stay app-managed in config.json.
+pub const SETTINGS_KEYS: &[&str] = &[
+    "poll_interval_ms",
+    "sort",
+    "size_thresholds",
+    "bot_prs",
+    "oauth_client_ids",
+    "token_expiry_warning_days",
+    "notifications",
+    "close_to_tray",
+    "adaptive_polling",
+];
+
+/// `RepoRules` fields, settable at the top level and in `[repo."org/name"]`.
+const RULE_KEYS: &[&str] = &["turn_rules", "filters", "sections"];
+
+const REPO_KEY: &str = "repo";
+
+/// Path of the team-shared file; only honoured in the user file. `~/` and
+/// paths relative to the user file are resolved.
+const TEAM_CONFIG_KEY: &str = "team_config";
+
+/// Where a value came from, from lowest to highest precedence.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigLayer {
+    /// Built into the app
+    Default,
+    /// The team-shared TOML file named by `team_config`
+    Team,
+    /// The user's config.toml
+    User,
+    /// Set in the app's own settings or by editing config.json; see
+    /// `AppConfig::set_in_app`
+    App,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LayerStatus {
+    pub layer: ConfigLayer,
+    pub path: Option<String>,
+    /// Whether the layer contributed to the result
+    pub loaded: bool,
+    pub error: Option<String>,
+}
+
+/// Result of stacking every layer over config.json. Returned as-is by
+/// `get_effective_config`.
+#[derive(Debug, Serialize)]
+pub struct LayeredConfig {
+    /// config.json with its settings replaced by the merged ones and the
+    /// rules filled in; tokens are untouched
+    #[serde(skip)]
+    pub config: AppConfig,
+    /// Merged settings and rules
+    pub values: Value,
+    /// Layer each value came from, keyed by dotted path, e.g. "sort.keys" or
+    /// `repo."org/name".filters.hide`
+    pub sources: BTreeMap<String, ConfigLayer>,
+    pub layers: Vec<LayerStatus>,
+}
+
+impl LayeredConfig {
+    /// Every layer's error, for display next to the settings.
+    pub fn error(&self) -> Option<String> {
+        let errors: Vec<String> = self.layers.iter().filter_map(|l| l.error.clone()).collect();
+        (!errors.is_empty()).then(|| errors.join("; "))
+    }
+}
+
+pub fn user_config_path() -> Result<PathBuf, String> {
+    Ok(config::get_config_dir()?.join(USER_CONFIG_FILE))
+}
+
+/// Stack defaults, the team file, the user's config.toml and settings changed
+/// in the app, in that order. A file that can't be read or parsed is skipped
+/// with an error; if the merged result doesn't validate, both TOML files are
+/// skipped so a bad edit never breaks the dashboard.
+pub fn apply_layers(config: AppConfig) -> LayeredConfig {
+    let mut layers: Vec<LayerStatus> = Vec::new();
+    let mut files: Vec<(ConfigLayer, Map<String, Value>)> = Vec::new();
+
+    let user_path = user_config_path().ok();
+    let user_doc = user_path.as_deref().map(read_toml_layer).transpose();
+    let (mut user_doc, mut user_error) = match user_doc {
+        Ok(doc) => (doc.flatten(), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let team_path = user_doc
+        .as_mut()
+        .and_then(|doc| doc.remove(TEAM_CONFIG_KEY))
+        .map(|value| match (value, user_path.as_deref().and_then(Path::parent)) {
+            (Value::String(raw), Some(user_dir)) => Ok(resolve_team_path(&raw, user_dir)),
+            _ => Err(format!("{}: {} must be a file path", USER_CONFIG_FILE, TEAM_CONFIG_KEY)),
+        });
+    match team_path {
+        Some(Ok(path)) => {
+            let doc = read_toml_layer(&path)
+                .and_then(|doc| {
+                    doc.ok_or_else(|| format!("Team config {} not found", path.display()))
+                })
+                .and_then(|doc| {
+                    check_keys(&doc)
+                        .map(|_| doc)
+                        .map_err(|e| format!("{}: {}", path.display(), e))
+                });
+            layers.push(LayerStatus {
+                layer: ConfigLayer::Team,
+                path: Some(path.display().to_string()),
+                loaded: doc.is_ok(),
+                error: doc.as_ref().err().cloned(),
+            });
+            if let Ok(doc) = doc {
+                files.push((ConfigLayer::Team, doc));
+            }
+        }
+        Some(Err(e)) => layers.push(LayerStatus {
+            layer: ConfigLayer::Team,
+            path: None,
+            loaded: false,
+            error: Some(e),
+        }),
+        None => {}
+    }
+
+    if let Some(doc) = user_doc.as_ref() {
+        if let Err(e) = check_keys(doc) {
+            user_error = Some(format!("{}: {}", USER_CONFIG_FILE, e));
+        }
+    }
+    let user_loaded = user_doc.is_some() && user_error.is_none();
+    layers.push(LayerStatus {
+        layer: ConfigLayer::User,
+        path: user_path.as_ref().map(|p| p.display().to_string()),
+        loaded: user_loaded,
+        error: user_error,
+    });
+    if let Some(doc) = user_doc.filter(|_| user_loaded) {
+        files.push((ConfigLayer::User, doc));
+    }
+
+    let (values, sources, result) = match merge_layers(&config, &files) {
+        (values, sources, Ok(merged)) => (values, sources, merged),
+        (_, _, Err(e)) => {
+            for layer in layers.iter_mut().filter(|l| l.loaded) {
+                layer.loaded = false;
+                layer.error = Some(format!("Not applied: {}", e));
+            }
+            let (values, sources, result) = merge_layers(&config, &[]);
+            // config.json on its own was validated when it was read
+            (values, sources, result.unwrap_or_else(|_| config.clone()))
+        }
+    };
+
+    LayeredConfig {
+        config: result,
+        values,
+        sources,
+        layers,
+    }
+}
+
+/// Merge the defaults, `files` and the app layer, and build the resulting
+/// config.
+fn merge_layers(
+    config: &AppConfig,
+    files: &[(ConfigLayer, Map<String, Value>)],
+) -> (Value, BTreeMap<String, ConfigLayer>, Result<AppConfig, String>) {
+    let mut values = Value::Object(Map::new());
+    let mut sources: BTreeMap<String, ConfigLayer> = BTreeMap::new();
+
+    let layers = std::iter::once((ConfigLayer::Default, default_layer()))
+        .chain(files.iter().cloned())
+        .chain(std::iter::once((ConfigLayer::App, app_layer(config))));
+    for (layer, doc) in layers {
+        let doc = Value::Object(doc);
+        record_sources(&doc, "", layer, &mut sources);
+        merge_values(&mut values, doc);
+    }
+
+    let result = build_config(config, &values);
+    (values, sources, result)
+}
+
+fn build_config(config: &AppConfig, values: &Value) -> Result<AppConfig, String> {
+    let mut doc = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    for key in SETTINGS_KEYS {
+        if let Some(value) = values.get(key) {
+            doc[key] = value.clone();
+        }
+    }
+    let mut merged: AppConfig = serde_json::from_value(doc).map_err(|e| e.to_string())?;
+    let errors = config::validate_config(&merged);
+    if !errors.is_empty() {
+        return Err(config::describe_field_errors(&errors));
+    }
+    merged.rules = build_rules(values)?;
+    Ok(merged)
+}
+
+/// Global rules, plus each repository table merged over them.
+fn build_rules(values: &Value) -> Result<Rules, String> {
+    let global_doc = Value::Object(pick(values, RULE_KEYS));
+    let global: RepoRules =
+        serde_json::from_value(global_doc.clone()).map_err(|e| e.to_string())?;
+
+    let mut repos: HashMap<String, RepoRules> = HashMap::new();
+    if let Some(Value::Object(overrides)) = values.get(REPO_KEY) {
+        for (repo, overlay) in overrides {
+            let mut doc = global_doc.clone();
+            merge_values(&mut doc, overlay.clone());
+            let rules = serde_json::from_value(doc)
+                .map_err(|e| format!("{}: {}", join_path(REPO_KEY, repo), e))?;
+            repos.insert(repo.to_lowercase(), rules);
+        }
+    }
+    Ok(Rules { global, repos })
+}
+
+fn default_layer() -> Map<String, Value> {
+    let mut doc = pick(&to_object(&AppConfig::default()), SETTINGS_KEYS);
+    doc.extend(to_object(&RepoRules::default()).as_object().cloned().unwrap_or_default());
+    doc
+}
+
+/// Settings in config.json that were set in the app. The rest are left at
+/// their default there and don't hide what the TOML files set.
+fn app_layer(config: &AppConfig) -> Map<String, Value> {
+    let keys: Vec<&str> = SETTINGS_KEYS
+        .iter()
+        .copied()
+        .filter(|key| config.set_in_app.contains(*key))
+        .collect();
+    pick(&to_object(config), &keys)
+}
+
+/// Settings whose value differs between two versions of config.json.
+pub fn changed_settings(previous: &AppConfig, config: &AppConfig) -> Vec<&'static str> {
+    let (previous, config) = (to_object(previous), to_object(config));
+    SETTINGS_KEYS
+        .iter()
+        .copied()
+        .filter(|key| previous.get(key) != config.get(key))
+        .collect()
+}
+
+fn to_object<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn pick(doc: &Value, keys: &[&str]) -> Map<String, Value> {
+    keys.iter()
+        .filter_map(|key| doc.get(key).map(|value| (key.to_string(), value.clone())))
+        .collect()
+}
+
+/// Deep-merge `overlay` into `base`: tables merge key by key, anything else
+/// (including arrays) replaces.
+fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Attribute every value in `overlay` to `layer`, dropping attributions for
+/// whatever it replaces.
+fn record_sources(
+    overlay: &Value,
+    path: &str,
+    layer: ConfigLayer,
+    sources: &mut BTreeMap<String, ConfigLayer>,
+) {
+    match overlay {
+        Value::Object(map) if !map.is_empty() => {
+            sources.remove(path);
+            for (key, value) in map {
+                record_sources(value, &join_path(path, key), layer, sources);
+            }
+        }
+        _ => {
+            let prefix = format!("{}.", path);
+            sources.retain(|p, _| !p.starts_with(&prefix));
+            sources.insert(path.to_string(), layer);
+        }
+    }
+}
+
+/// Append a key to a dotted path, quoting it the way TOML would
+/// (`repo."org/name"`).
+fn join_path(parent: &str, key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let key = if bare {
+        key.to_string()
+    } else {
+        format!("\"{}\"", key)
+    };
+    if parent.is_empty() {
+        key
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+/// Read a TOML layer as JSON. A missing file is `Ok(None)`.
+fn read_toml_layer(path: &Path) -> Result<Option<Map<String, Value>>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    toml::from_str(&contents)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// The team file config.toml names, if it names one, so the config watcher
+/// can follow it.
+pub fn team_config_path() -> Option<PathBuf> {
+    let user_path = user_config_path().ok()?;
+    let doc = read_toml_layer(&user_path).ok()??;
+    let raw = doc.get(TEAM_CONFIG_KEY)?.as_str()?;
+    Some(resolve_team_path(raw, user_path.parent()?))
+}
+
+fn resolve_team_path(raw: &str, user_dir: &Path) -> PathBuf {
+    if let Some(rest) = raw.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    let path = PathBuf::from(raw);
+    if path.is_absolute() {
+        path
+    } else {
+        user_dir.join(path)
+    }
+}
+
+/// Reject keys the TOML layers can't set, so typos don't go unnoticed.
+/// Nested rule fields are checked when the rules are deserialized.
+fn check_keys(doc: &Map<String, Value>) -> Result<(), String> {
+    for (key, value) in doc {
+        if SETTINGS_KEYS.contains(&key.as_str()) || RULE_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        if key != REPO_KEY {
+            return Err(if config_json_key(key) {
+                format!("\"{}\" can only be set in the app", key)
+            } else {
+                format!("Unknown key \"{}\"", key)
+            });
+        }
+
+        let Value::Object(repos) = value else {
+            return Err(format!("{} must be a table of repositories", REPO_KEY));
+        };
+        for (repo, overrides) in repos {
+            let path = join_path(REPO_KEY, repo);
+            if !repo.contains('/') {
+                return Err(format!("{}: expected \"owner/name\"", path));
+            }
+            let Value::Object(overrides) = overrides else {
+                return Err(format!("{} must be a table", path));
+            };
+            if let Some(key) = overrides
+                .keys()
+                .find(|key| !RULE_KEYS.contains(&key.as_str()))
+            {
+                return Err(format!(
+                    "{}: unknown key \"{}\"; expected one of {}",
+                    path,
+                    key,
+                    RULE_KEYS.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn config_json_key(key: &str) -> bool {
+    to_object(&AppConfig::default()).get(key).is_some()
+}
+
+/// The merged settings and rules, with the layer each value came from and
+/// the state of every file.
+#[tauri::command]
+pub fn get_effective_config() -> Result<LayeredConfig, String> {
+    Ok(apply_layers(config::read_config()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toml_layer(contents: &str) -> Map<String, Value> {
+        toml::from_str(contents).unwrap()
+    }
+
+    #[test]
+    fn later_layers_win_and_are_recorded() {
+        let team = toml_layer(
+            r#"
+            poll_interval_ms = 120000
+            [sort]
+            label_priority = ["urgent"]
+            "#,
+        );
+        let user = toml_layer("poll_interval_ms = 300000");
+        let files = [(ConfigLayer::Team, team), (ConfigLayer::User, user)];
+
+        let (_, sources, result) = merge_layers(&AppConfig::default(), &files);
+        let merged = result.unwrap();
+        assert_eq!(merged.poll_interval_ms, 300_000);
+        assert_eq!(merged.sort.label_priority, ["urgent"]);
+        assert_eq!(sources["poll_interval_ms"], ConfigLayer::User);
+        assert_eq!(sources["sort.label_priority"], ConfigLayer::Team);
+        assert_eq!(sources["close_to_tray"], ConfigLayer::Default);
+
+        // Only settings set in the app override the files
+        let mut app = AppConfig {
+            poll_interval_ms: 45_000,
+            close_to_tray: true,
+            ..AppConfig::default()
+        };
+        app.set_in_app.insert("poll_interval_ms".to_string());
+        let (_, sources, result) = merge_layers(&app, &files);
+        let merged = result.unwrap();
+        assert_eq!(merged.poll_interval_ms, 45_000);
+        assert!(!merged.close_to_tray);
+        assert_eq!(sources["poll_interval_ms"], ConfigLayer::App);
+    }
+
+    #[test]
+    fn repo_rules_merge_over_global_rules() {
+        let user = toml_layer(
+            r#"
+            [filters]
+            hide_drafts = true
+            hide_labels = ["wip"]
+            [repo."Org/Name".sections]
+            my-prs = false
+            [repo."Org/Name".filters]
+            hide_labels = ["skip"]
+            "#,
+        );
+        let (_, _, result) = merge_layers(&AppConfig::default(), &[(ConfigLayer::User, user)]);
+        let rules = result.unwrap().rules;
+
+        let repo = rules.for_repo("org/name");
+        assert!(repo.filters.hide_drafts);
+        assert_eq!(repo.filters.hide_labels, ["skip"]);
+        assert!(!repo.sections.my_prs);
+
+        let other = rules.for_repo("org/other");
+        assert_eq!(other.filters.hide_labels, ["wip"]);
+        assert!(other.sections.my_prs);
+    }
+
+    #[test]
+    fn invalid_merged_settings_are_an_error() {
+        let user = toml_layer("poll_interval_ms = 5");
+        let (_, _, result) = merge_layers(&AppConfig::default(), &[(ConfigLayer::User, user)]);
+        assert!(result.unwrap_err().contains("poll_interval_ms"));
+    }
+
+    #[test]
+    fn check_keys_rejects_what_toml_cannot_set() {
+        assert!(check_keys(&toml_layer("sort = { keys = [\"repo\"] }")).is_ok());
+        assert_eq!(
+            check_keys(&toml_layer("accounts = []")).unwrap_err(),
+            "\"accounts\" can only be set in the app"
+        );
+        assert_eq!(
+            check_keys(&toml_layer("poll_intervall_ms = 1")).unwrap_err(),
+            "Unknown key \"poll_intervall_ms\""
+        );
+        assert!(check_keys(&toml_layer("[repo.name]\nfilters = {}"))
+            .unwrap_err()
+            .contains("expected \"owner/name\""));
+        assert!(check_keys(&toml_layer("[repo.\"org/name\"]\nsort = {}"))
+            .unwrap_err()
+            .contains("unknown key \"sort\""));
+    }
+
+    #[test]
+    fn changed_settings_lists_differing_keys() {
+        let previous = AppConfig::default();
+        let config = AppConfig {
+            bot_prs: config::BotPrMode::Hide,
+            close_to_tray: true,
+            ..AppConfig::default()
+        };
+        assert_eq!(
+            changed_settings(&previous, &config),
+            ["bot_prs", "close_to_tray"]
+        );
+    }
+
+    #[test]
+    fn join_path_quotes_keys_toml_would() {
+        assert_eq!(join_path("", "sort"), "sort");
+        assert_eq!(join_path("repo", "org/name"), "repo.\"org/name\"");
+        assert_eq!(join_path("sections", "my-prs"), "sections.my-prs");
+    }
+}
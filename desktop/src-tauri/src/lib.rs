@@ -1,16 +1,67 @@
+mod adaptive;
+mod cache;
+mod changes;
 mod config;
+mod credentials;
 mod github;
+mod layers;
+mod notifications;
+mod oauth;
+mod poller;
+mod secrets;
+mod tray;
+
+use tauri::WindowEvent;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            // Before anything else reads config.json
+            if let Err(e) = config::recover_corrupt_config() {
+                eprintln!("Failed to recover config.json: {}", e);
+            }
+            notifications::init(app.handle());
+            // Without the watcher, hand edits apply on the next restart
+            if let Err(e) = config::watch_config(app.handle().clone()) {
+                eprintln!("Not watching config files: {}", e);
+            }
+            tray::init(app.handle())?;
+            poller::start(app.handle());
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                let close_to_tray = config::effective_config()
+                    .map(|c| c.close_to_tray)
+                    .unwrap_or(false);
+                if close_to_tray {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
+            cache::get_cached_dashboard,
             config::get_config,
-            config::save_pat,
-            config::save_poll_interval,
+            config::update_config,
+            config::save_accounts,
+            config::unlock_secret_store,
+            config::set_secret_store,
+            layers::get_effective_config,
             github::fetch_dashboard,
+            github::sort_dashboard,
             github::validate_pat,
+            oauth::start_device_flow,
+            oauth::complete_device_flow,
+            poller::pause,
+            poller::resume,
+            poller::refresh_now,
+            poller::refresh_pr,
+            poller::subscribe_dashboard,
+            poller::unsubscribe_dashboard,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
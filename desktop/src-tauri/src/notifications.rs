@@ -0,0 +1,144 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::changes::{DashboardChange, DashboardChangeKind};
+use crate::config::{self, NotificationConfig};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How many recent notifications stay clickable. Older ones are long gone
+/// from the notification center on most systems.
+const TRACKED_URLS: i32 = 100;
+
+/// Notification bookkeeping, managed as Tauri state.
+#[derive(Default)]
+struct Notifier {
+    next_id: AtomicI32,
+    /// PR URL of the last `TRACKED_URLS` notifications, by notification id,
+    /// so a click can open them
+    urls: Mutex<HashMap<i32, String>>,
+    /// When each recent notification was shown, for the rate cap
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+/// Register the click handler. Must run after the notification plugin is
+/// initialized.
+pub fn init(app: &AppHandle) {
+    app.manage(Notifier::default());
+    let handle = app.clone();
+    let _ = app.notification().on_action(move |action| {
+        if action.action_id() != "tap" {
+            return;
+        }
+        let Some(id) = action.notification().map(|n| n.id()) else {
+            return;
+        };
+        let url = handle
+            .state::<Notifier>()
+            .urls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+            .cloned();
+        if let Some(url) = url {
+            let _ = handle.opener().open_url(url, None::<&str>);
+        }
+    });
+}
+
+fn wants(config: &NotificationConfig, change: &DashboardChange) -> bool {
+    let own_pr_only = matches!(
+        change.kind,
+        DashboardChangeKind::NewApproval | DashboardChangeKind::ChangesRequested
+    );
+    config.events.contains(&change.kind)
+        && !(own_pr_only && change.section != "my-prs")
+        && !config
+            .muted_repos
+            .iter()
+            .any(|repo| repo.eq_ignore_ascii_case(&change.repo))
+}
+
+fn title(change: &DashboardChange) -> String {
+    let detail = change.detail.as_deref().unwrap_or_default();
+    match change.kind {
+        DashboardChangeKind::NewReviewRequest => "Review requested".to_string(),
+        DashboardChangeKind::BecameMyTurn => "Your turn".to_string(),
+        DashboardChangeKind::LeftDashboard => "Left the dashboard".to_string(),
+        DashboardChangeKind::NewApproval => format!("Approved by {}", detail),
+        DashboardChangeKind::ChangesRequested => format!("Changes requested by {}", detail),
+        DashboardChangeKind::CiFailed => "CI failed".to_string(),
+        DashboardChangeKind::MergeableStateChanged => format!("Mergeable state: {}", detail),
+    }
+}
+
+/// Show a native notification for each change the user asked for, up to
+/// `max_per_minute`. When there are more than fit, the last slot goes to a
+/// summary of the rest.
+pub fn notify_changes(app: &AppHandle, changes: &[DashboardChange]) {
+    let Ok(config) = config::effective_config() else {
+        return;
+    };
+    let config = config.notifications;
+    if !config.enabled {
+        return;
+    }
+    let notifier = app.state::<Notifier>();
+
+    let wanted: Vec<&DashboardChange> = changes.iter().filter(|c| wants(&config, c)).collect();
+    if wanted.is_empty() {
+        return;
+    }
+
+    let now = Instant::now();
+    let mut sent = notifier.sent.lock().unwrap_or_else(|e| e.into_inner());
+    while sent.front().is_some_and(|t| now.duration_since(*t) > RATE_WINDOW) {
+        sent.pop_front();
+    }
+    let room = (config.max_per_minute as usize).saturating_sub(sent.len());
+    let (shown, folded) = if wanted.len() <= room {
+        (&wanted[..], &[][..])
+    } else {
+        wanted.split_at(room.saturating_sub(1))
+    };
+
+    for change in shown {
+        let id = notifier.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut urls = notifier.urls.lock().unwrap_or_else(|e| e.into_inner());
+        urls.insert(id, change.url.clone());
+        // Ids are sequential, so this is the oldest one still tracked
+        urls.remove(&id.wrapping_sub(TRACKED_URLS));
+        drop(urls);
+        let shown = app
+            .notification()
+            .builder()
+            .id(id)
+            .title(title(change))
+            .body(format!("{}#{}: {}", change.repo, change.number, change.title))
+            .show();
+        if shown.is_ok() {
+            sent.push_back(now);
+        }
+    }
+    // Once the cap is reached, further changes wait for the dashboard
+    if room > 0 && !folded.is_empty() {
+        let summary = app
+            .notification()
+            .builder()
+            .title("PR Dashboard")
+            .body(format!(
+                "{} more {} on your dashboard",
+                folded.len(),
+                if folded.len() == 1 { "change" } else { "changes" }
+            ))
+            .show();
+        if summary.is_ok() {
+            sent.push_back(now);
+        }
+    }
+}
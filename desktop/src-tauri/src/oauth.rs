@@ -0,0 +1,249 @@
+use reqwest::header::{ACCEPT, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{self, ConfigView, GITHUB_API};
+
+/// Scopes the dashboard needs: private repo PRs and team review requests.
+const OAUTH_SCOPES: &str = "repo read:org";
+
+const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// The device flow currently being polled. Starting another sign-in cancels
+/// it, so only the newest flow can store a token.
+const FLOW_SUPERSEDED: &str = "Sign-in was replaced by a newer one.";
+
+static ACTIVE_FLOW: Mutex<Option<CancellationToken>> = Mutex::new(None);
+
+// ---------------------------------------------------------------------------
+// GitHub OAuth response types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    expires_in: u64,
+    interval: u64,
+}
+
+/// The token endpoint answers 200 for both success and pending states;
+/// exactly one of `access_token` / `error` is set.
+#[derive(Debug, Deserialize)]
+struct AccessTokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+    interval: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// Frontend types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceFlowStart {
+    pub device_code: String,
+    /// Code the user types at `verification_uri`
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// "https://api.github.com" -> "https://github.com",
+/// "https://ghes.example.com/api/v3" -> "https://ghes.example.com"
+pub fn web_base_url(api_base_url: &str) -> String {
+    let api_base_url = api_base_url.trim_end_matches('/');
+    if api_base_url == GITHUB_API {
+        return "https://github.com".to_string();
+    }
+    api_base_url
+        .strip_suffix("/api/v3")
+        .unwrap_or(api_base_url)
+        .to_string()
+}
+
+/// "https://ghes.example.com" -> "ghes.example.com"
+pub fn host_of(web_base_url: &str) -> &str {
+    web_base_url
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(web_base_url)
+}
+
+/// Cancel the flow being polled, if any, and make `token` the active one.
+/// Cancelling a flow that already finished does nothing.
+fn replace_active_flow(token: Option<CancellationToken>) {
+    let mut active = ACTIVE_FLOW.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(previous) = std::mem::replace(&mut *active, token) {
+        previous.cancel();
+    }
+}
+
+fn client_id_for(host: &str) -> Result<String, String> {
+    config::read_config()?
+        .oauth_client_ids
+        .get(host)
+        .filter(|id| !id.is_empty())
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "No OAuth client ID configured for {}. Add it under oauth_client_ids.",
+                host
+            )
+        })
+}
+
+async fn oauth_post<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+    form: &[(&str, &str)],
+) -> Result<T, String> {
+    let response = client
+        .post(url)
+        .header(ACCEPT, "application/json")
+        .header(USER_AGENT, "gh-dash-desktop")
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let body_preview: String = body.chars().take(200).collect();
+        return Err(format!(
+            "GitHub OAuth {}: {}",
+            status.as_u16(),
+            body_preview
+        ));
+    }
+
+    response
+        .json::<T>()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub OAuth response: {}", e))
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Step 1 of the device flow: request a device and user code. The frontend
+/// shows the user code and opens `verification_uri`, then calls
+/// `complete_device_flow`.
+#[tauri::command]
+pub async fn start_device_flow(
+    api_base_url: Option<String>,
+) -> Result<DeviceFlowStart, String> {
+    // A new code makes any flow still waiting on the old one pointless
+    replace_active_flow(None);
+    let web_url = web_base_url(api_base_url.as_deref().unwrap_or(GITHUB_API));
+    let client_id = client_id_for(host_of(&web_url))?;
+
+    let client = reqwest::Client::new();
+    let data: DeviceCodeResponse = oauth_post(
+        &client,
+        &format!("{}/login/device/code", web_url),
+        &[("client_id", &client_id), ("scope", OAUTH_SCOPES)],
+    )
+    .await?;
+
+    Ok(DeviceFlowStart {
+        device_code: data.device_code,
+        user_code: data.user_code,
+        verification_uri: data.verification_uri,
+        expires_in: data.expires_in,
+        interval: data.interval,
+    })
+}
+
+/// Step 2: poll until the user authorizes (or the code expires), then store
+/// the token. github.com tokens replace the single PAT; GHES tokens are saved
+/// as an account labelled with the host. Fails if another sign-in starts
+/// while this one is still waiting.
+#[tauri::command]
+pub async fn complete_device_flow(
+    app: AppHandle,
+    device_code: String,
+    interval: u64,
+    expires_in: u64,
+    api_base_url: Option<String>,
+) -> Result<ConfigView, String> {
+    let cancel = CancellationToken::new();
+    replace_active_flow(Some(cancel.clone()));
+
+    let api_base_url = api_base_url.unwrap_or_else(|| GITHUB_API.to_string());
+    let web_url = web_base_url(&api_base_url);
+    let host = host_of(&web_url).to_string();
+    let client_id = client_id_for(&host)?;
+
+    let client = reqwest::Client::new();
+    let token_url = format!("{}/login/oauth/access_token", web_url);
+    let deadline = Instant::now() + Duration::from_secs(expires_in);
+    let mut interval = Duration::from_secs(interval.max(1));
+
+    let token = loop {
+        if Instant::now() >= deadline {
+            return Err("Device code expired. Start sign-in again.".to_string());
+        }
+        let poll = async {
+            tokio::time::sleep(interval).await;
+            oauth_post::<AccessTokenResponse>(
+                &client,
+                &token_url,
+                &[
+                    ("client_id", &client_id),
+                    ("device_code", &device_code),
+                    ("grant_type", DEVICE_GRANT_TYPE),
+                ],
+            )
+            .await
+        };
+        let data = tokio::select! {
+            _ = cancel.cancelled() => return Err(FLOW_SUPERSEDED.to_string()),
+            data = poll => data?,
+        };
+
+        if let Some(token) = data.access_token {
+            break token;
+        }
+        match data.error.as_deref() {
+            Some("authorization_pending") => continue,
+            // GitHub asks us to back off; it sends the new interval to use
+            Some("slow_down") => {
+                let next = data.interval.unwrap_or(interval.as_secs() + 5);
+                interval = Duration::from_secs(next);
+            }
+            Some("expired_token") => {
+                return Err("Device code expired. Start sign-in again.".to_string());
+            }
+            Some("access_denied") => return Err("Sign-in was cancelled.".to_string()),
+            Some(other) => {
+                return Err(format!(
+                    "GitHub OAuth error: {}",
+                    data.error_description.as_deref().unwrap_or(other)
+                ));
+            }
+            None => {
+                return Err("GitHub OAuth returned neither a token nor an error".to_string());
+            }
+        }
+    };
+
+    // A newer sign-in may have started while the last request was in flight
+    if cancel.is_cancelled() {
+        return Err(FLOW_SUPERSEDED.to_string());
+    }
+    config::store_oauth_token(&app, &host, &api_base_url, token)
+}
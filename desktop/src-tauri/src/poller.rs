@@ -0,0 +1,604 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::adaptive;
+use crate::cache;
+use crate::changes;
+use crate::config::{self, AppConfig, CONFIG_CHANGED_EVENT};
+use crate::github::{self, DashboardPR, DashboardProgress, DashboardResponse, PrRefresh};
+use crate::layers;
+use crate::notifications;
+use crate::tray;
+
+/// Event emitted with the `DashboardResponse` after every successful fetch,
+/// its `changes` filled in against the previous one.
+pub const DASHBOARD_UPDATED_EVENT: &str = "dashboard-updated";
+
+/// Event emitted with an error message when a fetch fails; the last
+/// dashboard stays valid.
+pub const DASHBOARD_ERROR_EVENT: &str = "dashboard-error";
+
+/// Event emitted with an `OfflineStatus` instead of `DASHBOARD_ERROR_EVENT`
+/// when GitHub can't be reached; the UI keeps showing the last or cached
+/// dashboard.
+pub const DASHBOARD_OFFLINE_EVENT: &str = "dashboard-offline";
+
+/// Event emitted when a fetch starts, so the UI can show progress.
+pub const DASHBOARD_FETCHING_EVENT: &str = "dashboard-fetching";
+
+/// Event emitted with `true` or `false` when polling is paused or resumed,
+/// from the UI or the tray menu.
+pub const POLLING_PAUSED_EVENT: &str = "polling-paused";
+
+/// Used when the config can't be read.
+const FALLBACK_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Settings that change what a fetch returns. The sort order isn't one: the
+/// UI re-sorts the dashboard it has, and the next poll uses the new order.
+const FETCH_SETTINGS: &[&str] = &["size_thresholds", "bot_prs", "token_expiry_warning_days"];
+
+/// Settings that only change when the next poll happens.
+const SCHEDULE_SETTINGS: &[&str] = &["poll_interval_ms", "adaptive_polling"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OfflineStatus {
+    /// When the first fetch in the current run of network failures failed
+    pub since: String,
+    pub error: String,
+}
+
+/// Sent over the channels registered with `subscribe_dashboard` during each
+/// fetch: skeleton PRs per account once its searches return, each PR as its
+/// enrichment completes, then the final sorted dashboard, which is also
+/// emitted as `DASHBOARD_UPDATED_EVENT`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum DashboardStreamEvent {
+    #[serde(rename_all = "camelCase")]
+    Searched {
+        my_prs: Vec<DashboardPR>,
+        review_requests: Vec<DashboardPR>,
+        bot_prs: Vec<DashboardPR>,
+    },
+    Enriched {
+        pr: Box<DashboardPR>,
+    },
+    Done {
+        dashboard: Box<DashboardResponse>,
+    },
+}
+
+impl From<DashboardProgress> for DashboardStreamEvent {
+    fn from(progress: DashboardProgress) -> Self {
+        match progress {
+            DashboardProgress::Searched {
+                my_prs,
+                review_requests,
+                bot_prs,
+            } => Self::Searched {
+                my_prs,
+                review_requests,
+                bot_prs,
+            },
+            DashboardProgress::Enriched(pr) => Self::Enriched { pr },
+        }
+    }
+}
+
+/// Channels registered with `subscribe_dashboard`, by subscription id.
+#[derive(Default)]
+struct SubscriberList {
+    next_id: u64,
+    channels: HashMap<u64, Channel<DashboardStreamEvent>>,
+}
+
+type Subscribers = Arc<Mutex<SubscriberList>>;
+
+/// Send to every subscriber, dropping channels whose webview has gone away.
+fn broadcast(subscribers: &Subscribers, event: DashboardStreamEvent) {
+    subscribers
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .channels
+        .retain(|_, channel| channel.send(event.clone()).is_ok());
+}
+
+/// Refresh requests carry the generation of the latest fetch when they were
+/// made: a fetch of a later generation that completes satisfies them.
+enum PollCommand {
+    Pause,
+    /// Fetch now and restart the schedule
+    Resume {
+        after: u64,
+    },
+    /// Fetch now, whether or not polling is paused
+    RefreshNow {
+        after: u64,
+    },
+    /// Restart the wait for the next poll, which the new schedule applies to
+    Reschedule,
+}
+
+struct FetchState {
+    /// Generation of the latest fetch started, counting from 1
+    generation: u64,
+    /// Cancelled when a newer refresh supersedes that fetch
+    token: CancellationToken,
+}
+
+/// The fetch in flight, shared by the polling task and the `Poller` handle so
+/// a new refresh request can abort it instead of waiting for stale results.
+struct Fetches(Mutex<FetchState>);
+
+impl Default for Fetches {
+    fn default() -> Self {
+        Self(Mutex::new(FetchState {
+            generation: 0,
+            token: CancellationToken::new(),
+        }))
+    }
+}
+
+impl Fetches {
+    fn lock(&self) -> std::sync::MutexGuard<'_, FetchState> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start the next generation.
+    fn begin(&self) -> (u64, CancellationToken) {
+        let mut state = self.lock();
+        state.generation += 1;
+        state.token = CancellationToken::new();
+        (state.generation, state.token.clone())
+    }
+
+    /// Cancel the fetch in flight, if any, and return its generation. Dropping
+    /// the fetch also drops its outstanding `enrich_pr` futures.
+    fn supersede(&self) -> u64 {
+        let state = self.lock();
+        state.token.cancel();
+        state.generation
+    }
+}
+
+/// The last dashboard fetched, which the next one is diffed against and
+/// single-PR refreshes are merged into.
+type Snapshot = Arc<Mutex<Option<DashboardResponse>>>;
+
+/// The effective config as of the last `CONFIG_CHANGED_EVENT`, so the
+/// schedule doesn't read the config files on every tick. `None` when it
+/// couldn't be read.
+type ScheduleConfig = Arc<Mutex<Option<AppConfig>>>;
+
+/// Handle to the polling task, managed as Tauri state.
+pub struct Poller {
+    tx: mpsc::UnboundedSender<PollCommand>,
+    paused: Arc<AtomicBool>,
+    snapshot: Snapshot,
+    subscribers: Subscribers,
+    fetches: Arc<Fetches>,
+}
+
+impl Poller {
+    fn send(&self, command: PollCommand) -> Result<(), String> {
+        self.tx
+            .send(command)
+            .map_err(|_| "Dashboard polling has stopped".to_string())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn pause(&self) -> Result<(), String> {
+        self.send(PollCommand::Pause)
+    }
+
+    pub fn resume(&self) -> Result<(), String> {
+        let after = self.fetches.supersede();
+        self.send(PollCommand::Resume { after })
+    }
+
+    /// Fetch now, cancelling a fetch already in flight.
+    pub fn refresh_now(&self) -> Result<(), String> {
+        let after = self.fetches.supersede();
+        self.send(PollCommand::RefreshNow { after })
+    }
+}
+
+/// What a config change means for polling.
+#[derive(Debug, PartialEq, Eq)]
+enum ConfigImpact {
+    /// The dashboard shown or in flight no longer matches the config
+    Refetch,
+    /// Only the schedule changed
+    Reschedule,
+    /// Nothing polling depends on changed, e.g. the sort order or
+    /// notification settings
+    None,
+}
+
+fn config_impact(
+    previous: Option<&AppConfig>,
+    config: Option<&AppConfig>,
+    tokens_changed: bool,
+) -> ConfigImpact {
+    let (Some(previous), Some(config)) = (previous, config) else {
+        // Unreadable before or after; a fetch reports or clears the problem
+        return ConfigImpact::Refetch;
+    };
+    fn same<T: Serialize>(a: &T, b: &T) -> bool {
+        serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+    }
+    let settings = layers::changed_settings(previous, config);
+    let refetch = tokens_changed
+        // Includes plaintext tokens hand-edited into config.json
+        || previous.github_pat != config.github_pat
+        || !same(&previous.accounts, &config.accounts)
+        || previous.secret_store != config.secret_store
+        || !same(&previous.rules, &config.rules)
+        || settings.iter().any(|key| FETCH_SETTINGS.contains(key));
+    if refetch {
+        ConfigImpact::Refetch
+    } else if settings.iter().any(|key| SCHEDULE_SETTINGS.contains(key)) {
+        ConfigImpact::Reschedule
+    } else {
+        ConfigImpact::None
+    }
+}
+
+/// Start the polling task. It fetches every `poll_interval_ms`, or on the
+/// adaptive schedule when enabled, until paused. A config change to the
+/// accounts, tokens, rules or fetch settings refreshes straight away; a new
+/// schedule applies to the wait under way.
+pub fn start(app: &AppHandle) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let fetches = Arc::new(Fetches::default());
+    let schedule_config = ScheduleConfig::new(Mutex::new(config::effective_config().ok()));
+    let config_tx = tx.clone();
+    let config_fetches = Arc::clone(&fetches);
+    let changed_config = Arc::clone(&schedule_config);
+    let seen_tokens = AtomicU64::new(config::tokens_version());
+    app.listen(CONFIG_CHANGED_EVENT, move |_| {
+        let config = config::effective_config().ok();
+        let previous = std::mem::replace(
+            &mut *changed_config.lock().unwrap_or_else(|e| e.into_inner()),
+            config.clone(),
+        );
+        let tokens = config::tokens_version();
+        let tokens_changed = seen_tokens.swap(tokens, Ordering::Relaxed) != tokens;
+        let command = match config_impact(previous.as_ref(), config.as_ref(), tokens_changed) {
+            // A fetch still using the old token or accounts is abandoned
+            ConfigImpact::Refetch => PollCommand::RefreshNow {
+                after: config_fetches.supersede(),
+            },
+            ConfigImpact::Reschedule => PollCommand::Reschedule,
+            ConfigImpact::None => return,
+        };
+        let _ = config_tx.send(command);
+    });
+    let paused = Arc::new(AtomicBool::new(false));
+    let snapshot = Snapshot::default();
+    let subscribers = Subscribers::default();
+    app.manage(Poller {
+        tx,
+        paused: Arc::clone(&paused),
+        snapshot: Arc::clone(&snapshot),
+        subscribers: Arc::clone(&subscribers),
+        fetches: Arc::clone(&fetches),
+    });
+    tauri::async_runtime::spawn(run(
+        app.clone(),
+        rx,
+        paused,
+        snapshot,
+        subscribers,
+        fetches,
+        schedule_config,
+    ));
+}
+
+async fn run(
+    app: AppHandle,
+    mut rx: mpsc::UnboundedReceiver<PollCommand>,
+    paused: Arc<AtomicBool>,
+    snapshot: Snapshot,
+    subscribers: Subscribers,
+    fetches: Arc<Fetches>,
+    schedule_config: ScheduleConfig,
+) {
+    let mut offline_since: Option<String> = None;
+    // Show the cached dashboard in the tray until the first fetch lands; it
+    // isn't diffed against, so a restart doesn't replay old changes
+    if let Some(cached) = cache::load() {
+        tray::update(&app, &cached);
+    }
+    // A refresh still owed after the last fetch was cancelled
+    let mut queued = false;
+    // For adaptive polling, which slows down while nothing changes
+    let mut last_change = Instant::now();
+    loop {
+        let command = if queued {
+            queued = false;
+            Some(PollCommand::RefreshNow { after: 0 })
+        } else if paused.load(Ordering::Relaxed) {
+            rx.recv().await
+        } else {
+            let interval = poll_interval(&schedule_config, &snapshot, last_change.elapsed());
+            match tokio::time::timeout(interval, rx.recv()).await {
+                Ok(command) => command,
+                Err(_) => Some(PollCommand::RefreshNow { after: 0 }),
+            }
+        };
+        match command {
+            // Every sender is gone: the app is shutting down
+            None => return,
+            Some(PollCommand::Pause) => {
+                set_paused(&app, &paused, true);
+                continue;
+            }
+            Some(PollCommand::Resume { .. }) => set_paused(&app, &paused, false),
+            Some(PollCommand::RefreshNow { .. }) => {}
+            Some(PollCommand::Reschedule) => continue,
+        }
+
+        // Fetches run one at a time, so their results can't arrive out of
+        // order; a newer request cancels the one in flight instead
+        let (generation, token) = fetches.begin();
+        let completed = refresh(
+            &app,
+            generation,
+            &token,
+            &snapshot,
+            &subscribers,
+            &mut offline_since,
+        )
+        .await;
+        let changed = snapshot
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .is_some_and(|d| d.generation == generation && !d.changes.is_empty());
+        if changed {
+            last_change = Instant::now();
+        }
+
+        // Requests made before this fetch started are satisfied by it if it
+        // completed; the ones that cancelled it are not
+        while let Ok(command) = rx.try_recv() {
+            let after = match command {
+                PollCommand::Pause => {
+                    set_paused(&app, &paused, true);
+                    continue;
+                }
+                PollCommand::Resume { after } => {
+                    set_paused(&app, &paused, false);
+                    after
+                }
+                PollCommand::RefreshNow { after } => after,
+                // The next wait reads the schedule anew
+                PollCommand::Reschedule => continue,
+            };
+            if !completed || after >= generation {
+                queued = true;
+            }
+        }
+    }
+}
+
+fn set_paused(app: &AppHandle, paused: &AtomicBool, value: bool) {
+    if paused.swap(value, Ordering::Relaxed) != value {
+        let _ = app.emit(POLLING_PAUSED_EVENT, value);
+        tray::refresh_menu(app);
+    }
+}
+
+fn poll_interval(config: &ScheduleConfig, snapshot: &Snapshot, quiet_for: Duration) -> Duration {
+    let config = config.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(config) = config.as_ref() else {
+        return FALLBACK_POLL_INTERVAL;
+    };
+    let snapshot = snapshot.lock().unwrap_or_else(|e| e.into_inner());
+    adaptive::next_interval(config, snapshot.as_ref(), quiet_for)
+}
+
+/// Fetch, diff and publish one dashboard. Returns false when `token` was
+/// cancelled first, in which case nothing is published.
+async fn refresh(
+    app: &AppHandle,
+    generation: u64,
+    token: &CancellationToken,
+    snapshot: &Snapshot,
+    subscribers: &Subscribers,
+    offline_since: &mut Option<String>,
+) -> bool {
+    let _ = app.emit(DASHBOARD_FETCHING_EVENT, ());
+    // Run in its own task so a panic in the fetch fails this refresh instead
+    // of ending the polling task
+    let progress_subscribers = Arc::clone(subscribers);
+    let fetch = tauri::async_runtime::spawn(async move {
+        let progress =
+            |progress: DashboardProgress| broadcast(&progress_subscribers, progress.into());
+        github::fetch_dashboard_streaming(None, &progress).await
+    });
+    let abort = fetch.inner().abort_handle();
+    let result = tokio::select! {
+        result = fetch => result.unwrap_or_else(|e| Err(format!("Dashboard fetch failed: {}", e))),
+        _ = token.cancelled() => {
+            abort.abort();
+            return false;
+        }
+    };
+    match result {
+        Ok(mut dashboard) => {
+            dashboard.generation = generation;
+            let mut snapshot = snapshot.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(previous) = snapshot.as_ref() {
+                dashboard.changes = changes::diff_dashboards(previous, &dashboard);
+                notifications::notify_changes(app, &dashboard.changes);
+            }
+            tray::update(app, &dashboard);
+            broadcast(
+                subscribers,
+                DashboardStreamEvent::Done {
+                    dashboard: Box::new(dashboard.clone()),
+                },
+            );
+            let _ = app.emit(DASHBOARD_UPDATED_EVENT, &dashboard);
+            let _ = cache::save(&dashboard);
+            *snapshot = Some(dashboard);
+            *offline_since = None;
+        }
+        Err(e) if github::is_network_error(&e) => {
+            let since = offline_since.get_or_insert_with(github::chrono_now_iso);
+            let status = OfflineStatus {
+                since: since.clone(),
+                error: e,
+            };
+            let _ = app.emit(DASHBOARD_OFFLINE_EVENT, status);
+        }
+        Err(e) => {
+            // GitHub answered, so we're back online
+            *offline_since = None;
+            let _ = app.emit(DASHBOARD_ERROR_EVENT, e);
+        }
+    }
+    true
+}
+
+/// Stop scheduled fetches. `refresh_now` still works while paused.
+#[tauri::command]
+pub fn pause(poller: State<'_, Poller>) -> Result<(), String> {
+    poller.pause()
+}
+
+/// Fetch immediately and resume scheduled fetches.
+#[tauri::command]
+pub fn resume(poller: State<'_, Poller>) -> Result<(), String> {
+    poller.resume()
+}
+
+/// Stream each fetch's progress over `on_event`; see `DashboardStreamEvent`.
+/// Returns the id to pass to `unsubscribe_dashboard`.
+#[tauri::command]
+pub fn subscribe_dashboard(
+    poller: State<'_, Poller>,
+    on_event: Channel<DashboardStreamEvent>,
+) -> u64 {
+    let mut subscribers = poller.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+    subscribers.next_id += 1;
+    let id = subscribers.next_id;
+    subscribers.channels.insert(id, on_event);
+    id
+}
+
+/// Stop streaming to a channel registered with `subscribe_dashboard`.
+#[tauri::command]
+pub fn unsubscribe_dashboard(poller: State<'_, Poller>, id: u64) {
+    poller
+        .subscribers
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .channels
+        .remove(&id);
+}
+
+/// Fetch immediately; the result arrives as `DASHBOARD_UPDATED_EVENT` or
+/// `DASHBOARD_ERROR_EVENT`.
+#[tauri::command]
+pub fn refresh_now(poller: State<'_, Poller>) -> Result<(), String> {
+    poller.refresh_now()
+}
+
+/// A PR on the dashboard by repo and number, with its section and the login
+/// of the account that fetched it.
+fn find_pr(
+    dashboard: &DashboardResponse,
+    repo: &str,
+    number: u64,
+) -> Option<(DashboardPR, &'static str, String)> {
+    let my_prs = dashboard.my_prs.iter().map(|pr| (pr, "my-prs"));
+    let review_requests = dashboard
+        .review_requests
+        .iter()
+        .chain(&dashboard.bot_prs)
+        .map(|pr| (pr, "review-requests"));
+    let (pr, section) = my_prs
+        .chain(review_requests)
+        .find(|(pr, _)| pr.number == number && pr.repo.eq_ignore_ascii_case(repo))?;
+    let login = dashboard
+        .accounts
+        .iter()
+        .find(|a| a.label == pr.account)
+        .and_then(|a| a.login.clone())
+        .unwrap_or_else(|| dashboard.github_username.clone());
+    Some((pr.clone(), section, login))
+}
+
+/// Replace or, for a tombstone, remove a PR in every list it appears in.
+fn merge_pr(dashboard: &mut DashboardResponse, refresh: &PrRefresh) {
+    let lists = [
+        &mut dashboard.my_prs,
+        &mut dashboard.review_requests,
+        &mut dashboard.bot_prs,
+    ];
+    for list in lists {
+        match refresh {
+            PrRefresh::Open { pr } => {
+                for existing in list.iter_mut().filter(|p| p.url == pr.url) {
+                    *existing = (**pr).clone();
+                }
+            }
+            PrRefresh::Closed { url, .. } => list.retain(|p| &p.url != url),
+        }
+    }
+}
+
+/// Refresh one PR without a full dashboard fetch, e.g. right after acting on
+/// it, and merge the result into the backend's snapshot so the next poll
+/// doesn't report it as a change. Before the first fetch lands the PR is
+/// looked up in the cached dashboard the UI is showing, and nothing is
+/// merged.
+#[tauri::command]
+pub async fn refresh_pr(
+    app: AppHandle,
+    poller: State<'_, Poller>,
+    repo: String,
+    number: u64,
+) -> Result<PrRefresh, String> {
+    let live = poller
+        .snapshot
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|dashboard| {
+            find_pr(dashboard, &repo, number).map(|found| (found, Some(dashboard.generation)))
+        });
+    let found = live.or_else(|| {
+        cache::load().and_then(|cached| find_pr(&cached, &repo, number).map(|found| (found, None)))
+    });
+    let ((existing, section, login), generation) =
+        found.ok_or_else(|| format!("{}#{} is not on the dashboard", repo, number))?;
+
+    let refresh = github::fetch_single_pr(&existing, section, &login).await?;
+
+    let mut snapshot = poller.snapshot.lock().unwrap_or_else(|e| e.into_inner());
+    // A full refresh that landed meanwhile is newer; leave it unpatched
+    if let Some(dashboard) = snapshot
+        .as_mut()
+        .filter(|dashboard| Some(dashboard.generation) == generation)
+    {
+        merge_pr(dashboard, &refresh);
+        let _ = github::resort_dashboard(dashboard);
+        tray::update(&app, dashboard);
+        let _ = cache::save(dashboard);
+    }
+    Ok(refresh)
+}
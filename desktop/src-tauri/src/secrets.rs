@@ -0,0 +1,243 @@
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// ---------------------------------------------------------------------------
+// Store abstraction
+// ---------------------------------------------------------------------------
+
+/// Where tokens live instead of config.json.
+pub trait SecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String>;
+    fn set(&self, key: &str, value: &str) -> Result<(), String>;
+    fn delete(&self, key: &str) -> Result<(), String>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretStoreKind {
+    /// OS keyring: Secret Service on Linux, Keychain on macOS, Credential
+    /// Manager on Windows
+    #[default]
+    Keyring,
+    /// Passphrase-encrypted file next to config.json, for systems without a
+    /// keyring daemon
+    EncryptedFile,
+}
+
+/// Passphrase for the encrypted-file store, held in memory for the session.
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Environment variable that can supply the encrypted-file passphrase.
+const PASSPHRASE_ENV: &str = "GH_DASH_PASSPHRASE";
+
+pub fn set_passphrase(passphrase: String) {
+    *PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = Some(passphrase);
+}
+
+fn current_passphrase() -> Option<String> {
+    PASSPHRASE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .or_else(|| std::env::var(PASSPHRASE_ENV).ok())
+        .filter(|p| !p.is_empty())
+}
+
+pub fn open_store(kind: SecretStoreKind, dir: &Path) -> Result<Box<dyn SecretStore>, String> {
+    match kind {
+        SecretStoreKind::Keyring => Ok(Box::new(KeyringStore)),
+        SecretStoreKind::EncryptedFile => {
+            let passphrase = current_passphrase().ok_or_else(|| {
+                "SECRETS_LOCKED: Enter your passphrase to unlock stored tokens".to_string()
+            })?;
+            Ok(Box::new(EncryptedFileStore {
+                path: dir.join("secrets.enc"),
+                passphrase,
+            }))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OS keyring backend
+// ---------------------------------------------------------------------------
+
+const KEYRING_SERVICE: &str = "gh-dash";
+
+pub struct KeyringStore;
+
+impl KeyringStore {
+    fn entry(key: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYRING_SERVICE, key)
+            .map_err(|e| format!("Failed to open keyring entry: {}", e))
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        match Self::entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Failed to read from keyring: {}", e)),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        Self::entry(key)?
+            .set_password(value)
+            .map_err(|e| format!("Failed to write to keyring: {}", e))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        match Self::entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to delete from keyring: {}", e)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Encrypted file backend
+// ---------------------------------------------------------------------------
+
+/// On-disk layout of secrets.enc. The plaintext is a JSON map of key -> value,
+/// sealed with ChaCha20-Poly1305 under a key derived from the passphrase with
+/// Argon2id. Salt and nonce are regenerated on every write.
+#[derive(Serialize, Deserialize)]
+struct EncryptedFile {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    passphrase: String,
+}
+
+impl EncryptedFileStore {
+    fn derive_key(&self, salt: &[u8]) -> Result<Key, String> {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| format!("Failed to derive key: {}", e))?;
+        Ok(key)
+    }
+
+    fn read_all(&self) -> Result<HashMap<String, String>, String> {
+        if !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+        let contents = fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read secrets file: {}", e))?;
+        let file: EncryptedFile = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse secrets file: {}", e))?;
+        let decode = |field: &str| {
+            BASE64
+                .decode(field)
+                .map_err(|e| format!("Failed to decode secrets file: {}", e))
+        };
+        let salt = decode(&file.salt)?;
+        let nonce = decode(&file.nonce)?;
+        let ciphertext = decode(&file.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err("Failed to decode secrets file: bad nonce".to_string());
+        }
+
+        let cipher = ChaCha20Poly1305::new(&self.derive_key(&salt)?);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| "SECRETS_LOCKED: Wrong passphrase for stored tokens".to_string())?;
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| format!("Failed to parse decrypted secrets: {}", e))
+    }
+
+    fn write_all(&self, secrets: &HashMap<String, String>) -> Result<(), String> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let cipher = ChaCha20Poly1305::new(&self.derive_key(&salt)?);
+        let plaintext = serde_json::to_vec(secrets)
+            .map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_ref())
+            .map_err(|e| format!("Failed to encrypt secrets: {}", e))?;
+
+        let file = EncryptedFile {
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| format!("Failed to serialize secrets file: {}", e))?;
+        write_private_file(&self.path, json.as_bytes())
+            .map_err(|e| format!("Failed to write secrets file: {}", e))
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.read_all()?.remove(key))
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        let mut secrets = self.read_all()?;
+        secrets.insert(key.to_string(), value.to_string());
+        self.write_all(&secrets)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        let mut secrets = self.read_all()?;
+        if secrets.remove(key).is_some() {
+            self.write_all(&secrets)?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// File helpers
+// ---------------------------------------------------------------------------
+
+/// Replace a file with one readable only by the current user (0600 on Unix).
+/// The contents go to a sibling temp file that is fsynced and then renamed
+/// over `path`, so a crash leaves either the old file or the new one.
+pub fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // A stale temp file from a crashed write keeps its old mode otherwise
+        if tmp_path.exists() {
+            fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    let mut file = options.open(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)?;
+    // Persist the rename itself
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
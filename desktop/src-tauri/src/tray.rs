@@ -0,0 +1,194 @@
+use std::sync::Mutex;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::github::{DashboardResponse, TurnStatus};
+use crate::poller::Poller;
+
+const TRAY_ID: &str = "main";
+const MAX_MENU_PRS: usize = 5;
+const MAX_MENU_TITLE_CHARS: usize = 50;
+/// Prefix of the menu item IDs for PRs, followed by the PR URL
+const OPEN_PR_PREFIX: &str = "open:";
+
+/// What the tray shows from the latest dashboard.
+#[derive(Default)]
+struct TraySnapshot {
+    my_turn_count: usize,
+    /// Menu label and URL of the top my-turn PRs
+    top_prs: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+struct TrayState(Mutex<TraySnapshot>);
+
+/// Create the tray icon. Clicking it shows the main window; its menu lists
+/// the top my-turn PRs plus Refresh, Pause polling and Quit.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    app.manage(TrayState::default());
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("PR Dashboard")
+        .menu(&build_menu(app, &TraySnapshot::default())?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()))
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Update the count and PR list from a new dashboard.
+pub fn update(app: &AppHandle, dashboard: &DashboardResponse) {
+    let my_turn: Vec<_> = dashboard
+        .review_requests
+        .iter()
+        .chain(&dashboard.my_prs)
+        .filter(|pr| pr.turn_status == TurnStatus::MyTurn)
+        .collect();
+    let snapshot = TraySnapshot {
+        my_turn_count: my_turn.len(),
+        top_prs: my_turn
+            .iter()
+            .take(MAX_MENU_PRS)
+            .map(|pr| (menu_label(&pr.repo, pr.number, &pr.title), pr.url.clone()))
+            .collect(),
+    };
+    *app.state::<TrayState>()
+        .0
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = snapshot;
+    refresh_menu(app);
+}
+
+/// Rebuild the menu, tooltip and title, e.g. after polling was paused.
+pub fn refresh_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let state = app.state::<TrayState>();
+    let snapshot = state.0.lock().unwrap_or_else(|e| e.into_inner());
+
+    let count = snapshot.my_turn_count;
+    let tooltip = match count {
+        0 => "PR Dashboard: nothing needs your attention".to_string(),
+        1 => "PR Dashboard: 1 PR needs your attention".to_string(),
+        n => format!("PR Dashboard: {} PRs need your attention", n),
+    };
+    let _ = tray.set_tooltip(Some(&tooltip));
+    // Shown next to the icon on macOS and some Linux panels
+    let _ = tray.set_title((count > 0).then(|| count.to_string()).as_deref());
+    if let Ok(menu) = build_menu(app, &snapshot) {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
+
+fn build_menu(app: &AppHandle, snapshot: &TraySnapshot) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    if snapshot.top_prs.is_empty() {
+        menu.append(&MenuItem::with_id(
+            app,
+            "empty",
+            "Nothing needs your attention",
+            false,
+            None::<&str>,
+        )?)?;
+    }
+    for (label, url) in &snapshot.top_prs {
+        let id = format!("{}{}", OPEN_PR_PREFIX, url);
+        menu.append(&MenuItem::with_id(app, id, label, true, None::<&str>)?)?;
+    }
+    if snapshot.my_turn_count > snapshot.top_prs.len() {
+        let more = snapshot.my_turn_count - snapshot.top_prs.len();
+        menu.append(&MenuItem::with_id(
+            app,
+            "more",
+            format!("{} more in the dashboard…", more),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+
+    // The tray is created before the poller starts
+    let paused = app.try_state::<Poller>().is_some_and(|p| p.is_paused());
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "show",
+        "Show Dashboard",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "refresh",
+        "Refresh",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&CheckMenuItem::with_id(
+        app,
+        "pause",
+        "Pause polling",
+        true,
+        paused,
+        None::<&str>,
+    )?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    let poller = app.state::<Poller>();
+    match id {
+        "show" | "more" => show_main_window(app),
+        "refresh" => {
+            let _ = poller.refresh_now();
+        }
+        "pause" => {
+            let _ = if poller.is_paused() {
+                poller.resume()
+            } else {
+                poller.pause()
+            };
+        }
+        "quit" => app.exit(0),
+        _ => {
+            if let Some(url) = id.strip_prefix(OPEN_PR_PREFIX) {
+                let _ = app.opener().open_url(url, None::<&str>);
+            }
+        }
+    }
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// "org/repo#12 Title", with long titles shortened.
+fn menu_label(repo: &str, number: u64, title: &str) -> String {
+    let title = if title.chars().count() > MAX_MENU_TITLE_CHARS {
+        let short: String = title.chars().take(MAX_MENU_TITLE_CHARS - 1).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        title.to_string()
+    };
+    format!("{}#{} {}", repo, number, title)
+}
@@ -1,12 +1,24 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { Dashboard } from "@/components/Dashboard";
 import { PatSetup } from "@/components/PatSetup";
-import type { AppConfig } from "@/lib/types";
+import { SecretStoreUnlock } from "@/components/SecretStoreUnlock";
+import type { AppConfig, TokenSource } from "@/lib/types";
+
+const TOKEN_SOURCE_LABELS: Record<Exclude<TokenSource, "stored">, string> = {
+  "gh-dash-token": "GH_DASH_TOKEN",
+  "gh-token": "GH_TOKEN",
+  "github-token": "GITHUB_TOKEN",
+  "gh-enterprise-token": "GH_ENTERPRISE_TOKEN",
+  "github-enterprise-token": "GITHUB_ENTERPRISE_TOKEN",
+  "gh-cli": "the GitHub CLI login",
+};
 
 export default function App() {
   const [config, setConfig] = useState<AppConfig | null>(null);
   const [loading, setLoading] = useState(true);
+  const [reloadError, setReloadError] = useState<string | null>(null);
 
   useEffect(() => {
     invoke<AppConfig>("get_config")
@@ -20,6 +32,22 @@ export default function App() {
       });
   }, []);
 
+  // Settings changed from another window (or this one)
+  useEffect(() => {
+    const unlistenChanged = listen<AppConfig>("config-changed", (event) => {
+      setConfig(event.payload);
+      setReloadError(null);
+    });
+    // A hand edit to config.json was rejected; the old settings still apply
+    const unlistenError = listen<string>("config-error", (event) => {
+      setReloadError(event.payload);
+    });
+    return () => {
+      unlistenChanged.then((fn) => fn());
+      unlistenError.then((fn) => fn());
+    };
+  }, []);
+
   if (loading) {
     return (
       <main className="flex min-h-screen items-center justify-center">
@@ -28,17 +56,49 @@ export default function App() {
     );
   }
 
-  if (!config?.github_pat) {
+  const configError =
+    reloadError ?? config?.config_error ?? config?.layer_error;
+  const configNotice = configError && (
+    <p className="px-4 pt-4 text-xs text-destructive">{configError}</p>
+  );
+
+  // Tokens discovered in the environment or gh CLI are never saved
+  const discoveredSource =
+    config?.github_pat_source && config.github_pat_source !== "stored"
+      ? config.github_pat_source
+      : undefined;
+  const tokenSourceNotice = discoveredSource && (
+    <p className="px-4 pt-4 text-xs text-muted-foreground">
+      Using token from {TOKEN_SOURCE_LABELS[discoveredSource]}
+    </p>
+  );
+
+  if (config?.secret_store_error?.startsWith("SECRETS_LOCKED:")) {
+    return (
+      <main className="min-h-screen">
+        <SecretStoreUnlock onUnlock={(cfg) => setConfig(cfg)} />
+      </main>
+    );
+  }
+
+  if (!config?.has_github_pat && !config?.accounts.length) {
     return (
       <main className="min-h-screen">
-        <PatSetup onComplete={(cfg) => setConfig(cfg)} />
+        {configNotice}
+        <PatSetup
+          secretStore={config?.secret_store ?? "keyring"}
+          secretStoreError={config?.secret_store_error ?? null}
+          onComplete={(cfg) => setConfig(cfg)}
+        />
       </main>
     );
   }
 
   return (
     <main className="min-h-screen">
-      <Dashboard pat={config.github_pat} initialPollIntervalMs={config.poll_interval_ms} />
+      {configNotice}
+      {tokenSourceNotice}
+      <Dashboard config={config} onConfigChange={setConfig} />
     </main>
   );
 }
@@ -1,21 +1,30 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useMemo } from "react";
 import { PrSection } from "@/components/PrSection";
 import { ErrorMessage } from "@/components/ErrorMessage";
 import { DashboardSkeleton } from "@/components/DashboardSkeleton";
+import { Settings } from "@/components/Settings";
 import { useDashboardPolling } from "@/hooks/use-dashboard-polling";
 import { useViewMode } from "@/hooks/use-view-mode";
 import { usePollInterval, POLL_INTERVAL_OPTIONS } from "@/hooks/use-poll-interval";
-import { RefreshCw, CircleAlert, Clock, Columns2, Rows3, Pause, Play, Timer, Bug } from "lucide-react";
+import { useSortOrder, SORT_OPTIONS, CUSTOM_SORT } from "@/hooks/use-sort-order";
+import { RefreshCw, CircleAlert, Clock, Columns2, Rows3, Pause, Play, Timer, Bug, Bot, WifiOff, ArrowDownUp, Settings as SettingsIcon } from "lucide-react";
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
 import { Separator } from "@/components/ui/separator";
-import type { DashboardPR } from "@/lib/types";
+import { openUrl } from "@tauri-apps/plugin-opener";
+import { listen } from "@tauri-apps/api/event";
+import { describeSsoError, parseSsoError } from "@/lib/utils";
+import type { AppConfig, DashboardPR } from "@/lib/types";
 
 function timeAgoShort(date: Date): string {
   const seconds = Math.floor((Date.now() - date.getTime()) / 1000);
   if (seconds < 5) return "just now";
   if (seconds < 60) return `${seconds}s ago`;
   const minutes = Math.floor(seconds / 60);
-  return `${minutes}m ago`;
+  if (minutes < 60) return `${minutes}m ago`;
+  // Cached dashboards can be much older
+  const hours = Math.floor(minutes / 60);
+  if (hours < 24) return `${hours}h ago`;
+  return `${Math.floor(hours / 24)}d ago`;
 }
 
 function filterByTurn(prs: DashboardPR[], turn: "my-turn" | "their-turn") {
@@ -45,22 +54,43 @@ function SectionHeader({ title, turn, count }: SectionHeaderProps) {
 }
 
 interface DashboardProps {
-  pat: string;
-  initialPollIntervalMs: number;
+  config: AppConfig;
+  onConfigChange: (config: AppConfig) => void;
 }
 
-export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
+export function Dashboard({ config, onConfigChange }: DashboardProps) {
   const { viewMode, toggleViewMode } = useViewMode();
-  const { pollInterval, setPollInterval } = usePollInterval(initialPollIntervalMs);
+  const { pollInterval, setPollInterval } = usePollInterval(config.poll_interval_ms);
   const [autoPolling, setAutoPolling] = useState(true);
   const [showDebug, setShowDebug] = useState(false);
+  const [showSettings, setShowSettings] = useState(false);
 
-  const { data, error, isLoading, isRefreshing, refresh, lastFetchedAt } =
-    useDashboardPolling({
-      pat,
-      intervalMs: pollInterval,
-      enabled: autoPolling,
-    });
+  // The backend poller refetches on its own when the config changes
+  const {
+    data,
+    error,
+    isLoading,
+    isRefreshing,
+    refresh,
+    refreshPr,
+    resort,
+    lastFetchedAt,
+    offlineSince,
+  } = useDashboardPolling({
+    enabled: autoPolling,
+    pauseWhenHidden: !config.close_to_tray,
+  });
+  const { sortOption, setSortOption } = useSortOrder(config.sort, resort);
+
+  // Polling can also be paused from the tray menu
+  useEffect(() => {
+    const unlisten = listen<boolean>("polling-paused", (event) =>
+      setAutoPolling(!event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
 
   // Re-render every 5s so the "Updated Xs ago" text stays current
   const [, setTick] = useState(0);
@@ -80,6 +110,12 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
 
   const totalMyTurn = myPrsMyTurn.length + reviewMyTurn.length;
 
+  // Highlight PRs that changed since the previous fetch
+  const changedUrls = useMemo(
+    () => new Set(data?.changes.map((change) => change.url)),
+    [data],
+  );
+
   return (
     <div className={`w-full max-w-2xl mx-auto px-4 py-6 ${viewMode === "split" ? "lg:max-w-none lg:px-8" : "lg:max-w-4xl"}`}>
       <div className="flex items-center justify-between mb-6">
@@ -109,9 +145,35 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
               ))}
             </SelectContent>
           </Select>
+          <Select value={sortOption} onValueChange={setSortOption}>
+            <SelectTrigger className="w-[150px] h-8 text-xs">
+              <ArrowDownUp className="h-3 w-3 mr-1.5" />
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {SORT_OPTIONS.map((opt) => (
+                <SelectItem key={opt.value} value={opt.value}>
+                  {opt.label}
+                </SelectItem>
+              ))}
+              {sortOption === CUSTOM_SORT && (
+                <SelectItem value={CUSTOM_SORT} disabled>
+                  Custom
+                </SelectItem>
+              )}
+            </SelectContent>
+          </Select>
           {lastFetchedAt && (
-            <span className="text-xs text-muted-foreground">
+            <span
+              className="text-xs text-muted-foreground"
+              title={
+                data
+                  ? `Re-enriched ${data.refreshStats.enriched} of ${data.refreshStats.enriched + data.refreshStats.reused} PRs`
+                  : undefined
+              }
+            >
               Updated {timeAgoShort(lastFetchedAt)}
+              {data?.stale && " (cached)"}
             </span>
           )}
           <button
@@ -134,6 +196,14 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
           >
             <Bug className="h-4 w-4" />
           </button>
+          <button
+            onClick={() => setShowSettings((prev) => !prev)}
+            className={`p-2 rounded-md transition-colors ${showSettings ? "bg-accent text-accent-foreground" : "hover:bg-accent"}`}
+            aria-label={showSettings ? "Hide settings" : "Show settings"}
+            title={showSettings ? "Hide settings" : "Settings"}
+          >
+            <SettingsIcon className="h-4 w-4" />
+          </button>
           <button
             onClick={() => setAutoPolling((prev) => !prev)}
             className="p-2 rounded-md hover:bg-accent transition-colors"
@@ -159,9 +229,19 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
         </div>
       </div>
 
+      {showSettings && <Settings config={config} onChange={onConfigChange} />}
+
       {error && !data && <ErrorMessage error={error} onRetry={refresh} />}
 
-      {error && data && (
+      {offlineSince && data && (
+        <p className="text-xs text-muted-foreground mb-4 flex items-center gap-1.5">
+          <WifiOff className="h-3 w-3" />
+          Offline since {offlineSince.toLocaleTimeString()}. Showing the
+          dashboard from {timeAgoShort(new Date(data.fetchedAt))}.
+        </p>
+      )}
+
+      {error && data && !offlineSince && (
         <p className="text-xs text-destructive mb-4">
           {error.toLowerCase().includes("rate limit")
             ? "Rate limited by GitHub. Showing stale data. Try increasing the polling interval."
@@ -169,6 +249,43 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
         </p>
       )}
 
+      {data?.accounts
+        .filter((account) => account.error)
+        .map((account) => {
+          const sso = parseSsoError(account.error ?? "");
+          return (
+            <p key={account.label} className="text-xs text-destructive mb-4">
+              {account.label}: {sso ? describeSsoError(sso) : account.error}
+              {sso && (
+                <button
+                  onClick={() => openUrl(sso.url)}
+                  className="ml-1 underline hover:no-underline"
+                >
+                  Authorize
+                </button>
+              )}
+            </p>
+          );
+        })}
+
+      {data?.warnings.map((warning) => (
+        <p
+          key={`${warning.account}-${warning.kind}`}
+          className="text-xs text-amber-600 dark:text-amber-500 mb-4"
+        >
+          {data.accounts.length > 1 && `${warning.account}: `}
+          {warning.message}
+          {warning.url && (
+            <button
+              onClick={() => openUrl(warning.url!)}
+              className="ml-1 underline hover:no-underline"
+            >
+              Authorize
+            </button>
+          )}
+        </p>
+      ))}
+
       {isLoading && !data ? (
         <DashboardSkeleton />
       ) : (
@@ -179,22 +296,22 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
           <div className="space-y-8 lg:hidden">
             <section>
               <SectionHeader title="My PRs — My Turn" turn="my-turn" count={myPrsMyTurn.length} />
-              <PrSection prs={myPrsMyTurn} isLoading={false} emptyMessage="Nothing to respond to" showDebug={showDebug} />
+              <PrSection prs={myPrsMyTurn} isLoading={false} emptyMessage="Nothing to respond to" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <Separator />
             <section>
               <SectionHeader title="My PRs — Their Turn" turn="their-turn" count={myPrsTheirTurn.length} />
-              <PrSection prs={myPrsTheirTurn} isLoading={false} emptyMessage="No PRs waiting on others" showDebug={showDebug} />
+              <PrSection prs={myPrsTheirTurn} isLoading={false} emptyMessage="No PRs waiting on others" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <Separator />
             <section>
               <SectionHeader title="Review Requests — My Turn" turn="my-turn" count={reviewMyTurn.length} />
-              <PrSection prs={reviewMyTurn} isLoading={false} emptyMessage="No reviews needed from you" showDebug={showDebug} />
+              <PrSection prs={reviewMyTurn} isLoading={false} emptyMessage="No reviews needed from you" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <Separator />
             <section>
               <SectionHeader title="Review Requests — Their Turn" turn="their-turn" count={reviewTheirTurn.length} />
-              <PrSection prs={reviewTheirTurn} isLoading={false} emptyMessage="No reviews waiting on others" showDebug={showDebug} />
+              <PrSection prs={reviewTheirTurn} isLoading={false} emptyMessage="No reviews waiting on others" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
           </div>
 
@@ -202,22 +319,22 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
           <div className="hidden lg:grid lg:grid-cols-2 lg:gap-x-8 lg:gap-y-8">
             <section>
               <SectionHeader title="My PRs — My Turn" turn="my-turn" count={myPrsMyTurn.length} />
-              <PrSection prs={myPrsMyTurn} isLoading={false} emptyMessage="Nothing to respond to" showDebug={showDebug} />
+              <PrSection prs={myPrsMyTurn} isLoading={false} emptyMessage="Nothing to respond to" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <section>
               <SectionHeader title="My PRs — Their Turn" turn="their-turn" count={myPrsTheirTurn.length} />
-              <PrSection prs={myPrsTheirTurn} isLoading={false} emptyMessage="No PRs waiting on others" showDebug={showDebug} />
+              <PrSection prs={myPrsTheirTurn} isLoading={false} emptyMessage="No PRs waiting on others" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <div className="col-span-2">
               <Separator />
             </div>
             <section>
               <SectionHeader title="Review Requests — My Turn" turn="my-turn" count={reviewMyTurn.length} />
-              <PrSection prs={reviewMyTurn} isLoading={false} emptyMessage="No reviews needed from you" showDebug={showDebug} />
+              <PrSection prs={reviewMyTurn} isLoading={false} emptyMessage="No reviews needed from you" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <section>
               <SectionHeader title="Review Requests — Their Turn" turn="their-turn" count={reviewTheirTurn.length} />
-              <PrSection prs={reviewTheirTurn} isLoading={false} emptyMessage="No reviews waiting on others" showDebug={showDebug} />
+              <PrSection prs={reviewTheirTurn} isLoading={false} emptyMessage="No reviews waiting on others" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
           </div>
           </>
@@ -225,27 +342,43 @@ export function Dashboard({ pat, initialPollIntervalMs }: DashboardProps) {
           <div className="space-y-8">
             <section>
               <SectionHeader title="My PRs — My Turn" turn="my-turn" count={myPrsMyTurn.length} />
-              <PrSection prs={myPrsMyTurn} isLoading={false} emptyMessage="Nothing to respond to" showDebug={showDebug} />
+              <PrSection prs={myPrsMyTurn} isLoading={false} emptyMessage="Nothing to respond to" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <Separator />
             <section>
               <SectionHeader title="My PRs — Their Turn" turn="their-turn" count={myPrsTheirTurn.length} />
-              <PrSection prs={myPrsTheirTurn} isLoading={false} emptyMessage="No PRs waiting on others" showDebug={showDebug} />
+              <PrSection prs={myPrsTheirTurn} isLoading={false} emptyMessage="No PRs waiting on others" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <Separator />
             <section>
               <SectionHeader title="Review Requests — My Turn" turn="my-turn" count={reviewMyTurn.length} />
-              <PrSection prs={reviewMyTurn} isLoading={false} emptyMessage="No reviews needed from you" showDebug={showDebug} />
+              <PrSection prs={reviewMyTurn} isLoading={false} emptyMessage="No reviews needed from you" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
             <Separator />
             <section>
               <SectionHeader title="Review Requests — Their Turn" turn="their-turn" count={reviewTheirTurn.length} />
-              <PrSection prs={reviewTheirTurn} isLoading={false} emptyMessage="No reviews waiting on others" showDebug={showDebug} />
+              <PrSection prs={reviewTheirTurn} isLoading={false} emptyMessage="No reviews waiting on others" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
             </section>
           </div>
           )
         )
       )}
+
+      {data && data.botPrs.length > 0 && (
+        <div className="mt-8 space-y-8">
+          <Separator />
+          <section>
+            <div className="flex items-center gap-2 mb-3">
+              <Bot className="h-4 w-4 text-muted-foreground" />
+              <h2 className="text-sm font-semibold uppercase tracking-wide">
+                Bot PRs
+              </h2>
+              <span className="text-xs text-muted-foreground">({data.botPrs.length})</span>
+            </div>
+            <PrSection prs={data.botPrs} isLoading={false} emptyMessage="No bot PRs" showDebug={showDebug} changedUrls={changedUrls} onRefreshPr={refreshPr} />
+          </section>
+        </div>
+      )}
     </div>
   );
 }
@@ -0,0 +1,116 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { openUrl } from "@tauri-apps/plugin-opener";
+import { Github } from "lucide-react";
+import type { AppConfig, ConfigPatch, DeviceFlowStart } from "@/lib/types";
+
+interface DeviceFlowSignInProps {
+  onComplete: (config: AppConfig) => void;
+}
+
+/** "https://ghes.example.com/" -> "ghes.example.com" */
+function normalizeHost(input: string): string {
+  return input.trim().replace(/^https?:\/\//, "").replace(/\/.*$/, "");
+}
+
+export function DeviceFlowSignIn({ onComplete }: DeviceFlowSignInProps) {
+  const [flow, setFlow] = useState<DeviceFlowStart | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [useEnterprise, setUseEnterprise] = useState(false);
+  const [enterpriseHost, setEnterpriseHost] = useState("");
+  const [clientId, setClientId] = useState("");
+
+  const handleSignIn = async () => {
+    setError(null);
+
+    try {
+      const host = useEnterprise ? normalizeHost(enterpriseHost) : null;
+      // GHES needs its own OAuth App; save its client ID first
+      if (host && clientId.trim()) {
+        const current = await invoke<AppConfig>("get_config");
+        const patch: ConfigPatch = {
+          oauth_client_ids: { ...current.oauth_client_ids, [host]: clientId.trim() },
+        };
+        await invoke("update_config", { patch });
+      }
+      const apiBaseUrl = host ? `https://${host}/api/v3` : undefined;
+
+      const started = await invoke<DeviceFlowStart>("start_device_flow", {
+        apiBaseUrl,
+      });
+      setFlow(started);
+      await openUrl(started.verificationUri);
+      const config = await invoke<AppConfig>("complete_device_flow", {
+        deviceCode: started.deviceCode,
+        interval: started.interval,
+        expiresIn: started.expiresIn,
+        apiBaseUrl,
+      });
+      onComplete(config);
+    } catch (err) {
+      const message = typeof err === "string" ? err : String(err);
+      setError(message);
+      setFlow(null);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      {flow ? (
+        <div className="space-y-2">
+          <p className="text-sm text-muted-foreground">
+            Enter this code at{" "}
+            <a
+              href={flow.verificationUri}
+              target="_blank"
+              rel="noopener noreferrer"
+              className="underline"
+            >
+              {flow.verificationUri}
+            </a>
+          </p>
+          <p className="font-mono text-2xl tracking-widest">{flow.userCode}</p>
+          <p className="text-xs text-muted-foreground">Waiting for authorization...</p>
+        </div>
+      ) : (
+        <>
+          <button
+            onClick={handleSignIn}
+            disabled={useEnterprise && !normalizeHost(enterpriseHost)}
+            className="w-full inline-flex items-center justify-center gap-2 rounded-md border px-4 py-2 text-sm font-medium hover:bg-accent disabled:opacity-50"
+          >
+            <Github className="h-4 w-4" />
+            {useEnterprise ? "Sign in to GitHub Enterprise" : "Sign in with GitHub"}
+          </button>
+          <label className="flex items-center justify-center gap-2 text-xs text-muted-foreground">
+            <input
+              type="checkbox"
+              checked={useEnterprise}
+              onChange={(e) => setUseEnterprise(e.target.checked)}
+            />
+            GitHub Enterprise Server
+          </label>
+          {useEnterprise && (
+            <div className="space-y-2 text-left">
+              <input
+                type="text"
+                value={enterpriseHost}
+                onChange={(e) => setEnterpriseHost(e.target.value)}
+                placeholder="ghes.example.com"
+                className="w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <input
+                type="text"
+                value={clientId}
+                onChange={(e) => setClientId(e.target.value)}
+                placeholder="OAuth App client ID (if not configured yet)"
+                className="w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+          )}
+        </>
+      )}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
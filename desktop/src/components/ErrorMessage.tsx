@@ -1,5 +1,7 @@
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert";
-import { AlertCircle, Key, Timer } from "lucide-react";
+import { openUrl } from "@tauri-apps/plugin-opener";
+import { AlertCircle, Key, ShieldAlert, Timer } from "lucide-react";
+import { describeSsoError, parseSsoError } from "@/lib/utils";
 
 interface ErrorMessageProps {
   error: string;
@@ -7,6 +9,8 @@ interface ErrorMessageProps {
 }
 
 export function ErrorMessage({ error, onRetry }: ErrorMessageProps) {
+  const sso = parseSsoError(error);
+
   const isPatError =
     error.toLowerCase().includes("personal access token") ||
     error.toLowerCase().includes("pat") ||
@@ -18,7 +22,10 @@ export function ErrorMessage({ error, onRetry }: ErrorMessageProps) {
   let icon = <AlertCircle className="h-4 w-4" />;
   let title = "Error";
 
-  if (isRateLimitError) {
+  if (sso) {
+    icon = <ShieldAlert className="h-4 w-4" />;
+    title = "SAML SSO Required";
+  } else if (isRateLimitError) {
     icon = <Timer className="h-4 w-4" />;
     title = "Rate Limited";
   } else if (isPatError) {
@@ -31,8 +38,15 @@ export function ErrorMessage({ error, onRetry }: ErrorMessageProps) {
       {icon}
       <AlertTitle>{title}</AlertTitle>
       <AlertDescription className="mt-2">
-        <p>{error}</p>
-        {isRateLimitError ? (
+        <p>{sso ? describeSsoError(sso) : error}</p>
+        {sso ? (
+          <button
+            onClick={() => openUrl(sso.url)}
+            className="mt-2 text-sm underline font-medium hover:no-underline"
+          >
+            Authorize token
+          </button>
+        ) : isRateLimitError ? (
           <p className="mt-2 text-sm">
             Try increasing the polling interval using the timer dropdown above,
             or pause auto-refresh until the limit resets.
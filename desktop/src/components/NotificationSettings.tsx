@@ -0,0 +1,129 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { formatConfigUpdateError } from "@/lib/utils";
+import type {
+  AppConfig,
+  ConfigPatch,
+  DashboardChangeKind,
+  NotificationConfig,
+} from "@/lib/types";
+
+interface NotificationSettingsProps {
+  current: NotificationConfig;
+  onChange: (config: AppConfig) => void;
+}
+
+const EVENT_OPTIONS: { kind: DashboardChangeKind; label: string }[] = [
+  { kind: "became-my-turn", label: "It's my turn" },
+  { kind: "new-review-request", label: "New review request" },
+  { kind: "new-approval", label: "My PR approved" },
+  { kind: "changes-requested", label: "Changes requested on my PR" },
+  { kind: "ci-failed", label: "CI failed" },
+  { kind: "mergeable-state-changed", label: "Mergeable state changed" },
+  { kind: "left-dashboard", label: "PR left the dashboard" },
+];
+
+/** "a/b, c/d" -> ["a/b", "c/d"] */
+function parseRepos(input: string): string[] {
+  return input
+    .split(/[\s,]+/)
+    .map((repo) => repo.trim())
+    .filter(Boolean);
+}
+
+/**
+ * Which dashboard changes show a native notification. Toggles save straight
+ * away; the muted repositories and the cap save when the field loses focus.
+ */
+export function NotificationSettings({ current, onChange }: NotificationSettingsProps) {
+  const [mutedRepos, setMutedRepos] = useState(current.muted_repos.join(", "));
+  const [maxPerMinute, setMaxPerMinute] = useState(String(current.max_per_minute));
+  const [error, setError] = useState<string | null>(null);
+
+  // Follow changes made elsewhere, e.g. a hand edit to config.toml
+  useEffect(() => {
+    setMutedRepos(current.muted_repos.join(", "));
+    setMaxPerMinute(String(current.max_per_minute));
+  }, [current]);
+
+  const save = async (notifications: NotificationConfig) => {
+    setError(null);
+    try {
+      const patch: ConfigPatch = { notifications };
+      const config = await invoke<AppConfig>("update_config", { patch });
+      onChange(config);
+    } catch (err) {
+      setError(formatConfigUpdateError(err));
+    }
+  };
+
+  const toggleEvent = (kind: DashboardChangeKind, on: boolean) => {
+    const events = on
+      ? [...current.events, kind]
+      : current.events.filter((event) => event !== kind);
+    save({ ...current, events });
+  };
+
+  const saveMutedRepos = () => {
+    const muted_repos = parseRepos(mutedRepos);
+    if (muted_repos.join(",") === current.muted_repos.join(",")) return;
+    save({ ...current, muted_repos });
+  };
+
+  const saveMaxPerMinute = () => {
+    const max_per_minute = Number(maxPerMinute);
+    if (max_per_minute === current.max_per_minute) return;
+    // The backend checks the range
+    save({ ...current, max_per_minute });
+  };
+
+  return (
+    <div className="space-y-2 text-sm">
+      <label className="flex items-center gap-2">
+        <input
+          type="checkbox"
+          checked={current.enabled}
+          onChange={(e) => save({ ...current, enabled: e.target.checked })}
+        />
+        Show notifications
+      </label>
+      {current.enabled && (
+        <>
+          <div className="grid grid-cols-2 gap-1 pl-6">
+            {EVENT_OPTIONS.map(({ kind, label }) => (
+              <label key={kind} className="flex items-center gap-2 text-muted-foreground">
+                <input
+                  type="checkbox"
+                  checked={current.events.includes(kind)}
+                  onChange={(e) => toggleEvent(kind, e.target.checked)}
+                />
+                {label}
+              </label>
+            ))}
+          </div>
+          <input
+            type="text"
+            value={mutedRepos}
+            onChange={(e) => setMutedRepos(e.target.value)}
+            onBlur={saveMutedRepos}
+            placeholder="Muted repositories, e.g. owner/name, owner/other"
+            className="w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+          />
+          <label className="flex items-center gap-2 text-muted-foreground">
+            At most
+            <input
+              type="number"
+              min={1}
+              value={maxPerMinute}
+              onChange={(e) => setMaxPerMinute(e.target.value)}
+              onBlur={saveMaxPerMinute}
+              className="w-16 rounded-md border bg-background px-2 py-1 text-sm"
+            />
+            per minute; the rest are summed up in one
+          </label>
+        </>
+      )}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
@@ -2,20 +2,21 @@ import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { Github, Key, ExternalLink } from "lucide-react";
 import { Card, CardContent, CardHeader } from "@/components/ui/card";
-import type { AppConfig } from "@/lib/types";
-
-interface GitHubUser {
-  login: string;
-  avatar_url: string;
-  id: number;
-}
+import { DeviceFlowSignIn } from "@/components/DeviceFlowSignIn";
+import { SecretStoreSettings } from "@/components/SecretStoreSettings";
+import { formatConfigUpdateError } from "@/lib/utils";
+import type { AppConfig, ConfigPatch, SecretStoreKind, TokenInfo } from "@/lib/types";
 
 interface PatSetupProps {
+  secretStore: SecretStoreKind;
+  /** Set when the current store couldn't be opened, e.g. no keyring daemon */
+  secretStoreError: string | null;
   onComplete: (config: AppConfig) => void;
 }
 
-export function PatSetup({ onComplete }: PatSetupProps) {
+export function PatSetup({ secretStore, secretStoreError, onComplete }: PatSetupProps) {
   const [pat, setPat] = useState("");
+  const [showStorage, setShowStorage] = useState(secretStoreError !== null);
   const [error, setError] = useState<string | null>(null);
   const [validating, setValidating] = useState(false);
 
@@ -25,12 +26,19 @@ export function PatSetup({ onComplete }: PatSetupProps) {
     setValidating(true);
 
     try {
-      await invoke<GitHubUser>("validate_pat", { pat });
-      const config = await invoke<AppConfig>("save_pat", { pat });
+      const info = await invoke<TokenInfo>("validate_pat", { pat });
+      if (info.missingScopes.length > 0) {
+        setError(
+          `Token is missing required scopes: ${info.missingScopes.join(", ")}`
+        );
+        setValidating(false);
+        return;
+      }
+      const patch: ConfigPatch = { github_pat: pat };
+      const config = await invoke<AppConfig>("update_config", { patch });
       onComplete(config);
     } catch (err) {
-      const message = typeof err === "string" ? err : String(err);
-      setError(message);
+      setError(formatConfigUpdateError(err));
       setValidating(false);
     }
   };
@@ -44,6 +52,8 @@ export function PatSetup({ onComplete }: PatSetupProps) {
           See whose turn it is on every PR. Enter your GitHub Personal Access
           Token to get started.
         </p>
+        <DeviceFlowSignIn onComplete={onComplete} />
+        <p className="text-xs text-muted-foreground">or</p>
         <Card className="w-full">
           <CardHeader>
             <div className="flex items-center gap-2">
@@ -84,6 +94,27 @@ export function PatSetup({ onComplete }: PatSetupProps) {
             </form>
           </CardContent>
         </Card>
+        <div className="w-full space-y-2 text-left">
+          <button
+            type="button"
+            onClick={() => setShowStorage((prev) => !prev)}
+            className="text-xs text-muted-foreground underline hover:no-underline"
+          >
+            Where are tokens stored?
+          </button>
+          {showStorage && (
+            <>
+              {secretStoreError && (
+                <p className="text-xs text-destructive">{secretStoreError}</p>
+              )}
+              <p className="text-xs text-muted-foreground">
+                Without a working system keyring, store tokens in a
+                passphrase-encrypted file instead.
+              </p>
+              <SecretStoreSettings current={secretStore} onChange={onComplete} />
+            </>
+          )}
+        </div>
       </div>
     </div>
   );
@@ -1,15 +1,34 @@
+import { useState, type ReactNode } from "react";
 import { Card, CardContent, CardHeader } from "@/components/ui/card";
 import { Avatar, AvatarFallback, AvatarImage } from "@/components/ui/avatar";
 import { Badge } from "@/components/ui/badge";
-import { GitPullRequest } from "lucide-react";
+import { CircleCheck, CircleDot, CircleX, GitPullRequest, RefreshCw } from "lucide-react";
 import { TurnDebugPanel } from "@/components/TurnDebugPanel";
-import type { DashboardPR } from "@/lib/types";
+import type { CiStatus, DashboardPR, ReviewerState } from "@/lib/types";
 
 interface PrCardProps {
   pr: DashboardPR;
   showDebug?: boolean;
+  /** Changed in the latest fetch */
+  highlighted?: boolean;
+  /** Refresh just this PR */
+  onRefresh?: () => Promise<void>;
 }
 
+const CI_STATUS_ICON: Record<CiStatus, ReactNode> = {
+  success: <CircleCheck className="inline h-3.5 w-3.5 text-green-600" />,
+  pending: <CircleDot className="inline h-3.5 w-3.5 text-amber-500" />,
+  failure: <CircleX className="inline h-3.5 w-3.5 text-red-600" />,
+};
+
+const REVIEWER_STATE_RING: Record<ReviewerState, string> = {
+  approved: "ring-green-600",
+  "changes-requested": "ring-red-600",
+  commented: "ring-muted-foreground",
+  dismissed: "ring-muted",
+  pending: "ring-amber-500",
+};
+
 function timeAgo(dateStr: string): string {
   const seconds = Math.floor(
     (Date.now() - new Date(dateStr).getTime()) / 1000
@@ -24,16 +43,47 @@ function timeAgo(dateStr: string): string {
   return `${days}d ago`;
 }
 
-export function PrCard({ pr, showDebug }: PrCardProps) {
+export function PrCard({ pr, showDebug, highlighted, onRefresh }: PrCardProps) {
+  const [isRefreshing, setIsRefreshing] = useState(false);
+
+  const cardClasses = [
+    pr.isDraft && "border-dashed opacity-75",
+    highlighted && "ring-2 ring-primary/40",
+    pr.skeleton && "animate-pulse",
+  ]
+    .filter(Boolean)
+    .join(" ");
+
   return (
-    <Card className={pr.isDraft ? "border-dashed opacity-75" : ""}>
+    <Card className={cardClasses}>
       <CardHeader className="pb-2">
         <div className="flex items-center justify-between">
-          <span className="text-xs text-muted-foreground font-mono">
+          <span className="text-xs text-muted-foreground font-mono" title={pr.account}>
             {pr.repo}
           </span>
           <span className="text-xs text-muted-foreground">
+            {pr.waitingFor && (
+              <span title={pr.turnSince ?? undefined}>
+                waiting {pr.waitingFor} ·{" "}
+              </span>
+            )}
             {timeAgo(pr.updatedAt)}
+            {onRefresh && !pr.skeleton && (
+              <button
+                onClick={() => {
+                  setIsRefreshing(true);
+                  onRefresh().finally(() => setIsRefreshing(false));
+                }}
+                disabled={isRefreshing}
+                className="ml-1.5 align-middle hover:text-foreground disabled:opacity-50"
+                aria-label="Refresh this PR"
+                title="Refresh this PR"
+              >
+                <RefreshCw
+                  className={`inline h-3 w-3 ${isRefreshing ? "animate-spin" : ""}`}
+                />
+              </button>
+            )}
           </span>
         </div>
         <a
@@ -45,6 +95,11 @@ export function PrCard({ pr, showDebug }: PrCardProps) {
           <GitPullRequest className="inline h-3.5 w-3.5 mr-1 text-muted-foreground" />
           {pr.title}
           <span className="text-muted-foreground ml-1">#{pr.number}</span>
+          {pr.ciStatus && (
+            <span className="ml-1.5" title={`CI: ${pr.ciStatus}`}>
+              {CI_STATUS_ICON[pr.ciStatus]}
+            </span>
+          )}
         </a>
       </CardHeader>
       <CardContent className="pt-0">
@@ -65,11 +120,43 @@ export function PrCard({ pr, showDebug }: PrCardProps) {
               </Badge>
             )}
           </div>
+          {pr.size && (
+            <span
+              className="text-[10px] text-muted-foreground font-mono"
+              title={`${pr.size.changedFiles} files, ${pr.size.commits} commits`}
+            >
+              <Badge variant="secondary" className="text-[10px] px-1.5 py-0 mr-1.5">
+                {pr.size.bucket}
+              </Badge>
+              <span className="text-green-600">+{pr.size.additions}</span>{" "}
+              <span className="text-red-600">-{pr.size.deletions}</span>
+            </span>
+          )}
         </div>
         {pr.reviewSummary && (
-          <p className="text-xs text-muted-foreground mt-2">
-            {pr.reviewSummary}
-          </p>
+          <div className="flex items-center gap-2 mt-2">
+            {pr.reviewers.length > 0 && (
+              <div className="flex -space-x-1">
+                {pr.reviewers.map((reviewer) => (
+                  <Avatar
+                    key={reviewer.teamSlug ?? reviewer.login}
+                    className={`h-4 w-4 ring-2 ${REVIEWER_STATE_RING[reviewer.state]}`}
+                    title={`${reviewer.login}: ${reviewer.state}${reviewer.requestedAgain ? " (re-requested)" : ""}`}
+                  >
+                    {reviewer.avatarUrl && (
+                      <AvatarImage src={reviewer.avatarUrl} alt={reviewer.login} />
+                    )}
+                    <AvatarFallback className="text-[8px]">
+                      {reviewer.login.slice(0, 2).toUpperCase()}
+                    </AvatarFallback>
+                  </Avatar>
+                ))}
+              </div>
+            )}
+            <p className="text-xs text-muted-foreground">
+              {pr.reviewSummary}
+            </p>
+          </div>
         )}
         {pr.labels.length > 0 && (
           <div className="flex flex-wrap gap-1 mt-2">
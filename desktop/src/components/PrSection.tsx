@@ -8,9 +8,19 @@ interface PrSectionProps {
   isLoading: boolean;
   emptyMessage: string;
   showDebug?: boolean;
+  /** PRs that changed in the latest fetch */
+  changedUrls?: Set<string>;
+  onRefreshPr?: (pr: DashboardPR) => Promise<void>;
 }
 
-export function PrSection({ prs, isLoading, emptyMessage, showDebug }: PrSectionProps) {
+export function PrSection({
+  prs,
+  isLoading,
+  emptyMessage,
+  showDebug,
+  changedUrls,
+  onRefreshPr,
+}: PrSectionProps) {
   if (isLoading && !prs) {
     return <DashboardSkeleton />;
   }
@@ -27,7 +37,13 @@ export function PrSection({ prs, isLoading, emptyMessage, showDebug }: PrSection
   return (
     <div className="space-y-3">
       {prs.map((pr) => (
-        <PrCard key={pr.id} pr={pr} showDebug={showDebug} />
+        <PrCard
+          key={pr.url}
+          pr={pr}
+          showDebug={showDebug}
+          highlighted={changedUrls?.has(pr.url)}
+          onRefresh={onRefreshPr && (() => onRefreshPr(pr))}
+        />
       ))}
     </div>
   );
@@ -0,0 +1,73 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { AppConfig, SecretStoreKind } from "@/lib/types";
+
+interface SecretStoreSettingsProps {
+  current: SecretStoreKind;
+  onChange: (config: AppConfig) => void;
+}
+
+/**
+ * Choose where tokens are stored. The encrypted file is for systems without
+ * a working keyring; switching moves every stored token across.
+ */
+export function SecretStoreSettings({ current, onChange }: SecretStoreSettingsProps) {
+  const [kind, setKind] = useState<SecretStoreKind>(current);
+  const [passphrase, setPassphrase] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  const needsPassphrase = kind === "encrypted-file" && current !== "encrypted-file";
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setError(null);
+    setSaving(true);
+    try {
+      const config = await invoke<AppConfig>("set_secret_store", {
+        kind,
+        passphrase: needsPassphrase ? passphrase : null,
+      });
+      setPassphrase("");
+      onChange(config);
+    } catch (err) {
+      setError(typeof err === "string" ? err : String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-2">
+      <div className="flex items-center gap-2">
+        <select
+          value={kind}
+          onChange={(e) => setKind(e.target.value as SecretStoreKind)}
+          className="rounded-md border bg-background px-2 py-1 text-sm"
+        >
+          <option value="keyring">System keyring</option>
+          <option value="encrypted-file">Encrypted file</option>
+        </select>
+        {kind !== current && (
+          <button
+            type="submit"
+            disabled={saving || (needsPassphrase && !passphrase)}
+            className="rounded-md bg-primary px-3 py-1 text-sm font-medium text-primary-foreground hover:bg-primary/90 disabled:opacity-50"
+          >
+            {saving ? "Switching..." : "Switch"}
+          </button>
+        )}
+      </div>
+      {needsPassphrase && (
+        <input
+          type="password"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+          placeholder="Passphrase for the encrypted file"
+          className="w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+        />
+      )}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </form>
+  );
+}
@@ -0,0 +1,62 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Lock } from "lucide-react";
+import { Card, CardContent, CardHeader } from "@/components/ui/card";
+import type { AppConfig } from "@/lib/types";
+
+interface SecretStoreUnlockProps {
+  onUnlock: (config: AppConfig) => void;
+}
+
+export function SecretStoreUnlock({ onUnlock }: SecretStoreUnlockProps) {
+  const [passphrase, setPassphrase] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [unlocking, setUnlocking] = useState(false);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setError(null);
+    setUnlocking(true);
+
+    try {
+      const config = await invoke<AppConfig>("unlock_secret_store", { passphrase });
+      onUnlock(config);
+    } catch (err) {
+      const message = typeof err === "string" ? err : String(err);
+      setError(message.replace(/^SECRETS_LOCKED: /, ""));
+      setUnlocking(false);
+    }
+  };
+
+  return (
+    <div className="flex min-h-[80vh] flex-col items-center justify-center px-4">
+      <Card className="w-full max-w-md">
+        <CardHeader>
+          <div className="flex items-center gap-2">
+            <Lock className="h-4 w-4" />
+            <span className="font-medium">Unlock stored tokens</span>
+          </div>
+        </CardHeader>
+        <CardContent>
+          <form onSubmit={handleSubmit} className="space-y-4">
+            <input
+              type="password"
+              value={passphrase}
+              onChange={(e) => setPassphrase(e.target.value)}
+              placeholder="Passphrase"
+              className="w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            {error && <p className="text-sm text-destructive">{error}</p>}
+            <button
+              type="submit"
+              disabled={!passphrase || unlocking}
+              className="w-full rounded-md bg-primary px-4 py-2 text-sm font-medium text-primary-foreground hover:bg-primary/90 disabled:opacity-50"
+            >
+              {unlocking ? "Unlocking..." : "Unlock"}
+            </button>
+          </form>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
@@ -0,0 +1,74 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Card, CardContent, CardHeader } from "@/components/ui/card";
+import { NotificationSettings } from "@/components/NotificationSettings";
+import { SecretStoreSettings } from "@/components/SecretStoreSettings";
+import { formatConfigUpdateError } from "@/lib/utils";
+import type { AppConfig, ConfigPatch } from "@/lib/types";
+
+interface SettingsProps {
+  config: AppConfig;
+  onChange: (config: AppConfig) => void;
+}
+
+function SettingsRow({ label, children }: { label: string; children: React.ReactNode }) {
+  return (
+    <div className="space-y-1.5">
+      <p className="text-xs font-semibold uppercase tracking-wide text-muted-foreground">
+        {label}
+      </p>
+      {children}
+    </div>
+  );
+}
+
+/** Closing the window hides it to the tray, where polling keeps going. */
+function CloseToTraySetting({ config, onChange }: SettingsProps) {
+  const [error, setError] = useState<string | null>(null);
+
+  const save = async (close_to_tray: boolean) => {
+    setError(null);
+    try {
+      const patch: ConfigPatch = { close_to_tray };
+      onChange(await invoke<AppConfig>("update_config", { patch }));
+    } catch (err) {
+      setError(formatConfigUpdateError(err));
+    }
+  };
+
+  return (
+    <div className="space-y-1 text-sm">
+      <label className="flex items-center gap-2">
+        <input
+          type="checkbox"
+          checked={config.close_to_tray}
+          onChange={(e) => save(e.target.checked)}
+        />
+        Keep running in the tray when the window is closed
+      </label>
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+/** Settings that aren't in the dashboard header. Changes apply immediately. */
+export function Settings({ config, onChange }: SettingsProps) {
+  return (
+    <Card className="mb-6">
+      <CardHeader>
+        <span className="font-medium">Settings</span>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <SettingsRow label="Window">
+          <CloseToTraySetting config={config} onChange={onChange} />
+        </SettingsRow>
+        <SettingsRow label="Notifications">
+          <NotificationSettings current={config.notifications} onChange={onChange} />
+        </SettingsRow>
+        <SettingsRow label="Token storage">
+          <SecretStoreSettings current={config.secret_store} onChange={onChange} />
+        </SettingsRow>
+      </CardContent>
+    </Card>
+  );
+}
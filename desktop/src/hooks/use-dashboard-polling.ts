@@ -1,11 +1,63 @@
 import { useState, useEffect, useCallback, useRef } from "react";
-import { invoke } from "@tauri-apps/api/core";
-import type { DashboardResponse } from "@/lib/types";
+import { Channel, invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type {
+  DashboardPR,
+  DashboardResponse,
+  DashboardStreamEvent,
+  OfflineStatus,
+  PrRefresh,
+  SortConfig,
+} from "@/lib/types";
 
 interface UseDashboardPollingOptions {
-  pat: string;
-  intervalMs: number;
   enabled?: boolean;
+  /** Pause while the window is hidden; off when it closes to the tray */
+  pauseWhenHidden?: boolean;
+}
+
+/** Replace a refreshed PR in place, or drop it when it was closed. */
+function mergePr(data: DashboardResponse, result: PrRefresh): DashboardResponse {
+  const merge = (prs: DashboardPR[]) =>
+    result.status === "open"
+      ? prs.map((pr) => (pr.url === result.pr.url ? result.pr : pr))
+      : prs.filter((pr) => pr.url !== result.url);
+  return {
+    ...data,
+    myPrs: merge(data.myPrs),
+    reviewRequests: merge(data.reviewRequests),
+    botPrs: merge(data.botPrs),
+  };
+}
+
+/** Add skeleton PRs from one account's searches, skipping ones already shown. */
+function addSkeletons(
+  data: DashboardResponse | null,
+  event: Extract<DashboardStreamEvent, { kind: "searched" }>,
+): DashboardResponse {
+  const base: DashboardResponse = data ?? {
+    myPrs: [],
+    reviewRequests: [],
+    botPrs: [],
+    githubUsername: "",
+    accounts: [],
+    warnings: [],
+    changes: [],
+    fetchedAt: new Date().toISOString(),
+    stale: false,
+    refreshStats: { enriched: 0, reused: 0 },
+    generation: 0,
+  };
+  const seen = new Set(
+    [...base.myPrs, ...base.reviewRequests, ...base.botPrs].map((pr) => pr.url),
+  );
+  const fresh = (prs: DashboardPR[]) => prs.filter((pr) => !seen.has(pr.url));
+  return {
+    ...base,
+    myPrs: [...base.myPrs, ...fresh(event.myPrs)],
+    reviewRequests: [...base.reviewRequests, ...fresh(event.reviewRequests)],
+    botPrs: [...base.botPrs, ...fresh(event.botPrs)],
+  };
 }
 
 interface UseDashboardPollingResult {
@@ -14,95 +66,210 @@ interface UseDashboardPollingResult {
   isLoading: boolean;
   isRefreshing: boolean;
   refresh: () => void;
+  /** Refresh one PR without a full fetch, e.g. right after acting on it */
+  refreshPr: (pr: DashboardPR) => Promise<void>;
+  /** Re-sort the shown dashboard locally, without a fetch */
+  resort: (sort: SortConfig) => Promise<void>;
   lastFetchedAt: Date | null;
+  /** Set while GitHub is unreachable */
+  offlineSince: Date | null;
 }
 
+/**
+ * Follows the backend poller, which fetches on the configured interval and
+ * whenever the config changes. `enabled` pauses and resumes its schedule.
+ * The last cached dashboard is shown until the first live fetch lands;
+ * without one, the first fetch fills in progressively as it streams.
+ */
 export function useDashboardPolling({
-  pat,
-  intervalMs,
   enabled = true,
+  pauseWhenHidden = true,
 }: UseDashboardPollingOptions): UseDashboardPollingResult {
   const [data, setData] = useState<DashboardResponse | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [isRefreshing, setIsRefreshing] = useState(false);
   const [lastFetchedAt, setLastFetchedAt] = useState<Date | null>(null);
-  const intervalRef = useRef<ReturnType<typeof setInterval> | null>(null);
+  const [offlineSince, setOfflineSince] = useState<Date | null>(null);
+  const [listening, setListening] = useState(false);
   const isFirstFetch = useRef(true);
+  const latestGeneration = useRef(0);
+  const dataRef = useRef<DashboardResponse | null>(null);
 
-  const fetchData = useCallback(async () => {
-    if (isFirstFetch.current) {
-      setIsLoading(true);
-    } else {
-      setIsRefreshing(true);
-    }
+  useEffect(() => {
+    dataRef.current = data;
+  }, [data]);
 
-    try {
-      const result = await invoke<DashboardResponse>("fetch_dashboard", { pat });
-      setData(result);
-      setError(null);
-      setLastFetchedAt(new Date());
-    } catch (err) {
-      const message = typeof err === "string" ? err : String(err);
-      setError(message);
+  useEffect(() => {
+    const unlistenFetching = listen("dashboard-fetching", () => {
+      if (!isFirstFetch.current) {
+        setIsRefreshing(true);
+      }
+    });
+    const channel = new Channel<DashboardStreamEvent>();
+    channel.onmessage = (event) => {
+      switch (event.kind) {
+        // Partial results only matter until the first fetch completes;
+        // later fetches replace the dashboard in one go
+        case "searched":
+          if (!isFirstFetch.current) return;
+          setData((current) => (current?.stale ? current : addSkeletons(current, event)));
+          setIsLoading(false);
+          break;
+        case "enriched":
+          if (!isFirstFetch.current) return;
+          setData(
+            (current) =>
+              current && mergePr(current, { status: "open", pr: event.pr }),
+          );
+          break;
+        case "done":
+          // The backend cancels superseded fetches; never let an older
+          // result replace a newer one regardless
+          if (event.dashboard.generation < latestGeneration.current) return;
+          latestGeneration.current = event.dashboard.generation;
+          setData(event.dashboard);
+          setError(null);
+          setLastFetchedAt(new Date());
+          setOfflineSince(null);
+          setIsLoading(false);
+          setIsRefreshing(false);
+          isFirstFetch.current = false;
+          break;
+      }
+    };
+    const subscribed = invoke<number>("subscribe_dashboard", { onEvent: channel });
+    const unlistenError = listen<string>("dashboard-error", (event) => {
+      setError(event.payload);
+      setOfflineSince(null);
       if (isFirstFetch.current) {
         setData(null);
       }
-    } finally {
       setIsLoading(false);
       setIsRefreshing(false);
       isFirstFetch.current = false;
-    }
-  }, [pat]);
+    });
+    // Unlike other errors, keeps a cached dashboard on screen
+    const unlistenOffline = listen<OfflineStatus>(
+      "dashboard-offline",
+      (event) => {
+        setError(event.payload.error);
+        setOfflineSince(new Date(event.payload.since));
+        setIsLoading(false);
+        setIsRefreshing(false);
+        isFirstFetch.current = false;
+      },
+    );
 
-  const startPolling = useCallback(() => {
-    if (intervalRef.current) {
-      clearInterval(intervalRef.current);
-    }
-    intervalRef.current = setInterval(fetchData, intervalMs);
-  }, [fetchData, intervalMs]);
+    invoke<DashboardResponse | null>("get_cached_dashboard")
+      .then((cached) => {
+        // A live result may already have arrived
+        if (!cached || !isFirstFetch.current) return;
+        setData((current) => current ?? cached);
+        setLastFetchedAt((current) => current ?? new Date(cached.fetchedAt));
+        setIsLoading(false);
+      })
+      .catch((err) => console.error("Failed to load cached dashboard:", err));
 
-  const stopPolling = useCallback(() => {
-    if (intervalRef.current) {
-      clearInterval(intervalRef.current);
-      intervalRef.current = null;
-    }
+    // Don't start fetching until every listener is in place
+    Promise.all([
+      unlistenFetching,
+      subscribed,
+      unlistenError,
+      unlistenOffline,
+    ]).then(() => setListening(true));
+    return () => {
+      unlistenFetching.then((fn) => fn());
+      channel.onmessage = () => {};
+      subscribed
+        .then((id) => invoke("unsubscribe_dashboard", { id }))
+        .catch((err) => console.error("Failed to unsubscribe from dashboard:", err));
+      unlistenError.then((fn) => fn());
+      unlistenOffline.then((fn) => fn());
+    };
   }, []);
 
+  // Resuming fetches straight away, which also covers the initial load
+  useEffect(() => {
+    if (!listening) return;
+    invoke(enabled ? "resume" : "pause").catch((err) =>
+      console.error("Failed to update polling:", err),
+    );
+  }, [listening, enabled]);
+
   const refresh = useCallback(() => {
-    fetchData();
-    if (enabled) {
-      startPolling();
+    invoke("refresh_now").catch((err) =>
+      console.error("Failed to refresh dashboard:", err),
+    );
+  }, []);
+
+  const refreshPr = useCallback(async (pr: DashboardPR) => {
+    try {
+      const result = await invoke<PrRefresh>("refresh_pr", {
+        repo: pr.repo,
+        number: pr.number,
+      });
+      setData((current) => current && mergePr(current, result));
+    } catch (err) {
+      console.error(`Failed to refresh ${pr.repo}#${pr.number}:`, err);
     }
-  }, [fetchData, startPolling, enabled]);
+  }, []);
 
-  // Initial fetch + start polling
+  const resort = useCallback(async (sort: SortConfig) => {
+    const current = dataRef.current;
+    if (!current) return;
+    const sorted = await invoke<DashboardResponse>("sort_dashboard", {
+      dashboard: current,
+      sort,
+    });
+    // A fetch may have landed meanwhile; it is sorted already
+    setData((latest) => (latest === current ? sorted : latest));
+  }, []);
+
+  // Pause polling when window is hidden, resume when visible. With
+  // close_to_tray it keeps going so the tray stays current. Only a pause
+  // made here is undone here: the backend reports it as "polling-paused",
+  // which turns `enabled` off until the window is shown again.
+  const enabledRef = useRef(enabled);
+  const pausedWhileHidden = useRef(false);
   useEffect(() => {
-    fetchData();
-    if (enabled) {
-      startPolling();
-    }
-    return stopPolling;
-  }, [fetchData, startPolling, stopPolling, enabled]);
+    enabledRef.current = enabled;
+  }, [enabled]);
 
-  // Pause polling when window is hidden, resume when visible
   useEffect(() => {
-    if (!enabled) return;
+    if (!listening || !pauseWhenHidden) return;
 
     const handleVisibilityChange = () => {
-      if (document.hidden) {
-        stopPolling();
+      let command: "pause" | "resume";
+      if (document.hidden && enabledRef.current) {
+        pausedWhileHidden.current = true;
+        command = "pause";
+      } else if (!document.hidden && pausedWhileHidden.current) {
+        pausedWhileHidden.current = false;
+        command = "resume";
       } else {
-        fetchData();
-        startPolling();
+        return;
       }
+      invoke(command).catch((err) =>
+        console.error("Failed to update polling:", err),
+      );
     };
 
     document.addEventListener("visibilitychange", handleVisibilityChange);
     return () => {
       document.removeEventListener("visibilitychange", handleVisibilityChange);
     };
-  }, [enabled, fetchData, startPolling, stopPolling]);
+  }, [listening, pauseWhenHidden]);
 
-  return { data, error, isLoading, isRefreshing, refresh, lastFetchedAt };
+  return {
+    data,
+    error,
+    isLoading,
+    isRefreshing,
+    refresh,
+    refreshPr,
+    resort,
+    lastFetchedAt,
+    offlineSince,
+  };
 }
@@ -1,5 +1,6 @@
-import { useState, useCallback } from "react";
+import { useState, useCallback, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import type { ConfigPatch } from "@/lib/types";
 
 export const POLL_INTERVAL_OPTIONS = [
   { value: 30_000, label: "30s" },
@@ -11,10 +12,16 @@ export const POLL_INTERVAL_OPTIONS = [
 export function usePollInterval(initialInterval: number = 60_000) {
   const [pollInterval, setPollIntervalState] = useState(initialInterval);
 
+  // Follow changes made elsewhere, e.g. a hand edit to config.json
+  useEffect(() => {
+    setPollIntervalState(initialInterval);
+  }, [initialInterval]);
+
   const setPollInterval = useCallback(async (ms: number) => {
     setPollIntervalState(ms);
     try {
-      await invoke("save_poll_interval", { intervalMs: ms });
+      const patch: ConfigPatch = { poll_interval_ms: ms };
+      await invoke("update_config", { patch });
     } catch (err) {
       console.error("Failed to save poll interval:", err);
     }
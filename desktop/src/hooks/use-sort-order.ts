@@ -0,0 +1,59 @@
+import { useState, useCallback, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { ConfigPatch, SortConfig, SortKey } from "@/lib/types";
+
+export const SORT_OPTIONS = [
+  { value: "turn", label: "My turn first", keys: ["turn-status", "recently-updated"] },
+  { value: "recent", label: "Recently updated", keys: ["recently-updated"] },
+  { value: "oldest", label: "Oldest waiting", keys: ["oldest-waiting"] },
+  { value: "size", label: "Smallest first", keys: ["size", "recently-updated"] },
+  { value: "repo", label: "By repository", keys: ["repo", "turn-status", "recently-updated"] },
+] as const satisfies readonly { value: string; label: string; keys: readonly SortKey[] }[];
+
+/** Keys set in config.toml or by hand that match no preset. */
+export const CUSTOM_SORT = "custom";
+
+function optionFor(sort: SortConfig): string {
+  const match = SORT_OPTIONS.find(
+    (opt) =>
+      opt.keys.length === sort.keys.length &&
+      opt.keys.every((key, i) => key === sort.keys[i]),
+  );
+  return match && Object.keys(sort.sections).length === 0 ? match.value : CUSTOM_SORT;
+}
+
+/**
+ * The dashboard ordering. Choosing a preset re-sorts the shown dashboard
+ * straight away through `resort`, then saves it for later fetches; it
+ * replaces any per-section orderings.
+ */
+export function useSortOrder(
+  sort: SortConfig,
+  resort: (sort: SortConfig) => Promise<void>,
+) {
+  const [sortOption, setSortOptionState] = useState(() => optionFor(sort));
+
+  // Follow changes made elsewhere, e.g. a hand edit to config.json
+  useEffect(() => {
+    setSortOptionState(optionFor(sort));
+  }, [sort]);
+
+  const setSortOption = useCallback(
+    async (value: string) => {
+      const option = SORT_OPTIONS.find((opt) => opt.value === value);
+      if (!option) return;
+      setSortOptionState(value);
+      const next: SortConfig = { ...sort, keys: [...option.keys], sections: {} };
+      try {
+        await resort(next);
+        const patch: ConfigPatch = { sort: next };
+        await invoke("update_config", { patch });
+      } catch (err) {
+        console.error("Failed to change sort order:", err);
+      }
+    },
+    [sort, resort],
+  );
+
+  return { sortOption, setSortOption } as const;
+}
@@ -12,38 +12,319 @@ export interface TurnDebugInfo {
   decidingCheck: string;
 }
 
+export type SizeBucket = "XS" | "S" | "M" | "L" | "XL";
+
+export interface PrSize {
+  additions: number;
+  deletions: number;
+  changedFiles: number;
+  commits: number;
+  bucket: SizeBucket;
+}
+
+export type ReviewerState =
+  | "approved"
+  | "changes-requested"
+  | "commented"
+  | "dismissed"
+  | "pending";
+
+export interface DashboardReviewer {
+  login: string;
+  avatarUrl: string | null;
+  teamSlug: string | null;
+  state: ReviewerState;
+  submittedAt: string | null;
+  requestedAgain: boolean;
+}
+
 export interface DashboardPR {
   id: number;
   number: number;
   title: string;
   url: string;
   repo: string;
+  account: string;
   author: {
     login: string;
     avatarUrl: string;
+    isBot: boolean;
   };
   turnStatus: TurnStatus;
   turnDebugInfo?: TurnDebugInfo;
   isDraft: boolean;
   createdAt: string;
   updatedAt: string;
+  turnSince: string | null;
+  waitingFor: string | null;
   labels: Array<{
     name: string;
     color: string;
   }>;
+  reviewers: DashboardReviewer[];
   reviewSummary: string;
+  size: PrSize | null;
+  mergeableState: string | null;
+  ciStatus: CiStatus | null;
+  /** Search result only, shown while enrichment runs */
+  skeleton: boolean;
+}
+
+export type CiStatus = "success" | "pending" | "failure";
+
+export type DashboardChangeKind =
+  | "new-review-request"
+  | "became-my-turn"
+  | "left-dashboard"
+  | "new-approval"
+  | "changes-requested"
+  | "ci-failed"
+  | "mergeable-state-changed";
+
+/** One difference from the previous fetch. */
+export interface DashboardChange {
+  kind: DashboardChangeKind;
+  url: string;
+  repo: string;
+  number: number;
+  title: string;
+  account: string;
+  section: "my-prs" | "review-requests";
+  detail: string | null;
 }
 
 export interface DashboardResponse {
   myPrs: DashboardPR[];
   reviewRequests: DashboardPR[];
+  botPrs: DashboardPR[];
   githubUsername: string;
+  accounts: DashboardAccountStatus[];
+  warnings: DashboardWarning[];
+  changes: DashboardChange[];
   fetchedAt: string;
+  /** Served from the on-disk cache rather than a live fetch */
+  stale: boolean;
+  refreshStats: RefreshStats;
+  /** Poller fetch number, increasing; 0 for cached or one-off fetches */
+  generation: number;
+}
+
+/** How many PRs a refresh fetched details for, versus reused from the last one. */
+export interface RefreshStats {
+  enriched: number;
+  reused: number;
+}
+
+/** Sent over the `subscribe_dashboard` channel while a fetch runs. */
+export type DashboardStreamEvent =
+  /** One account's search results, before enrichment */
+  | {
+      kind: "searched";
+      myPrs: DashboardPR[];
+      reviewRequests: DashboardPR[];
+      botPrs: DashboardPR[];
+    }
+  | { kind: "enriched"; pr: DashboardPR }
+  /** The final sorted dashboard, also emitted as `dashboard-updated` */
+  | { kind: "done"; dashboard: DashboardResponse };
+
+/** Result of the `refresh_pr` command. */
+export type PrRefresh =
+  | { status: "open"; pr: DashboardPR }
+  /** Tombstone for a PR closed or merged since the last refresh */
+  | { status: "closed"; url: string; merged: boolean };
+
+/** Payload of the `dashboard-offline` event. */
+export interface OfflineStatus {
+  /** When GitHub first became unreachable (ISO-8601) */
+  since: string;
+  error: string;
+}
+
+export interface DashboardAccountStatus {
+  label: string;
+  login: string | null;
+  error: string | null;
+  tokenExpiresAt: string | null;
+}
+
+export type DashboardWarningKind =
+  | "token-expiring"
+  | "missing-scopes"
+  | "sso-required";
+
+export interface DashboardWarning {
+  kind: DashboardWarningKind;
+  account: string;
+  message: string;
+  url: string | null;
+}
+
+/** Payload of an "SSO_REQUIRED: {...}" error from the backend. */
+export interface SsoRequired {
+  organization: string | null;
+  url: string;
+}
+
+export type TokenType = "classic" | "fine-grained" | "oauth" | "unknown";
+
+export interface TokenInfo {
+  user: {
+    login: string;
+    avatar_url: string;
+    id: number;
+  };
+  tokenType: TokenType;
+  scopes: string[];
+  acceptedScopes: string[];
+  missingScopes: string[];
+  expiresAt: string | null;
+}
+
+export type SortKey =
+  | "turn-status"
+  | "recently-updated"
+  | "oldest-waiting"
+  | "repo"
+  | "label-priority"
+  | "draft-last"
+  | "size";
+
+export interface SortConfig {
+  keys: SortKey[];
+  sections: Partial<Record<"my-prs" | "review-requests", SortKey[]>>;
+  label_priority: string[];
 }
 
+export interface SizeThresholds {
+  xs: number;
+  s: number;
+  m: number;
+  l: number;
+}
+
+export interface GitHubAccount {
+  /** Stable key for the stored token; empty for a new account */
+  id: string;
+  label: string;
+  token: string;
+  api_base_url: string;
+}
+
+export interface NotificationConfig {
+  enabled: boolean;
+  /** Approvals and change requests only notify for your own PRs */
+  events: DashboardChangeKind[];
+  muted_repos: string[];
+  max_per_minute: number;
+}
+
+/** Local hours; a start after the end spans midnight. */
+export interface WorkingHours {
+  start_hour: number;
+  end_hour: number;
+  weekdays_only: boolean;
+}
+
+/** Faster polling while your PRs are active, slower when idle or rate limited. */
+export interface AdaptivePolling {
+  enabled: boolean;
+  min_interval_ms: number;
+  max_interval_ms: number;
+  working_hours: WorkingHours | null;
+}
+
+export type BotPrMode = "show" | "hide" | "group" | "deprioritize";
+
+export type AuthMethod = "oauth" | "pat";
+
+export interface DeviceFlowStart {
+  deviceCode: string;
+  userCode: string;
+  verificationUri: string;
+  expiresIn: number;
+  interval: number;
+}
+
+export type SecretStoreKind = "keyring" | "encrypted-file";
+
+/** Where a token in use came from: the app's own store, or discovered. */
+export type TokenSource =
+  | "stored"
+  | "gh-dash-token"
+  | "gh-token"
+  | "github-token"
+  | "gh-enterprise-token"
+  | "github-enterprise-token"
+  | "gh-cli";
+
+/** Config as returned by the backend: tokens are always blank. */
 export interface AppConfig {
+  version: number;
   github_pat: string;
+  has_github_pat: boolean;
+  github_pat_source: TokenSource | null;
+  account_token_sources: Record<string, TokenSource>;
+  secret_store: SecretStoreKind;
+  secret_store_error: string | null;
+  config_error: string | null;
+  layer_error: string | null;
   poll_interval_ms: number;
+  sort: SortConfig;
+  size_thresholds: SizeThresholds;
+  bot_prs: BotPrMode;
+  accounts: GitHubAccount[];
+  auth_method: AuthMethod;
+  oauth_client_ids: Record<string, string>;
+  token_expiry_warning_days: number;
+  notifications: NotificationConfig;
+  close_to_tray: boolean;
+  adaptive_polling: AdaptivePolling;
+  /** Settings that override config.toml and the team config */
+  set_in_app: string[];
+}
+
+/** Partial settings update accepted by the `update_config` command. */
+export interface ConfigPatch {
+  github_pat?: string;
+  poll_interval_ms?: number;
+  sort?: SortConfig;
+  size_thresholds?: SizeThresholds;
+  bot_prs?: BotPrMode;
+  oauth_client_ids?: Record<string, string>;
+  token_expiry_warning_days?: number;
+  notifications?: NotificationConfig;
+  close_to_tray?: boolean;
+  adaptive_polling?: AdaptivePolling;
+}
+
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+/** Rejection value of `update_config`. */
+export interface ConfigUpdateError {
+  message: string;
+  fields: FieldError[];
 }
 
 export type ViewMode = "unified" | "split";
+
+/** Layer a config value came from, lowest precedence first. */
+export type ConfigLayer = "default" | "team" | "user" | "app";
+
+export interface ConfigLayerStatus {
+  layer: ConfigLayer;
+  path: string | null;
+  loaded: boolean;
+  error: string | null;
+}
+
+/** Result of the `get_effective_config` command. */
+export interface EffectiveConfig {
+  values: Record<string, unknown>;
+  /** Keyed by dotted path, e.g. `repo."org/name".filters.hide` */
+  sources: Record<string, ConfigLayer>;
+  layers: ConfigLayerStatus[];
+}
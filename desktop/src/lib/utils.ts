@@ -1,6 +1,34 @@
 import { clsx, type ClassValue } from "clsx";
 import { twMerge } from "tailwind-merge";
+import type { ConfigUpdateError, SsoRequired } from "@/lib/types";
 
 export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs));
 }
+
+/** Human-readable text for an `update_config` rejection. */
+export function formatConfigUpdateError(err: unknown): string {
+  if (typeof err === "string") return err;
+  const { message, fields } = err as ConfigUpdateError;
+  if (!fields?.length) return message ?? String(err);
+  return fields.map((f) => `${f.field}: ${f.message}`).join("; ");
+}
+
+const SSO_REQUIRED_PREFIX = "SSO_REQUIRED: ";
+
+/** Extract the SAML SSO authorization details from a backend error, if any. */
+export function parseSsoError(error: string): SsoRequired | null {
+  const start = error.indexOf(SSO_REQUIRED_PREFIX);
+  if (start === -1) return null;
+  try {
+    return JSON.parse(error.slice(start + SSO_REQUIRED_PREFIX.length));
+  } catch {
+    return null;
+  }
+}
+
+export function describeSsoError(sso: SsoRequired): string {
+  return sso.organization
+    ? `The ${sso.organization} organization requires SAML SSO. Authorize your token to see its PRs.`
+    : "An organization requires SAML SSO. Authorize your token to see its PRs.";
+}